@@ -1,29 +1,53 @@
 use eframe::egui;
-use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager};
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use crate::commands;
+use crate::desktop::Desktop;
+use crate::keys;
+use crate::kwin;
+use crate::modifier_tap::TapMonitor;
+use crate::monitors;
 use crate::settings::{HotkeyConfig, Settings, SettingsWindow};
+use crate::validation::{self, Severity};
+use crate::window_backend::{self, SystemRunner, WindowBackend};
+use crate::wlroots;
 
 // Tray menu action codes
 const TRAY_NONE: u8 = 0;
 const TRAY_TOGGLE: u8 = 1;
 const TRAY_SETTINGS: u8 = 2;
 const TRAY_QUIT: u8 = 3;
+const TRAY_PROFILE: u8 = 4;
+
+/// Most words listed under the command bar for "#tag" input.
+const TAG_LIST_MAX: usize = 8;
 
 pub struct LauncherApp {
-    hotkey_manager: GlobalHotKeyManager,
+    desktop: Desktop,
+    window: Box<dyn WindowBackend>,
+    /// None when there is no X server to grab keys on (pure Wayland session);
+    /// the compositor-side binding is all we have then.
+    hotkey_manager: Option<GlobalHotKeyManager>,
     toggle_hotkey_id: u32,
+    /// X11 hotkeys registered for individual magic words, with their keyword.
+    word_hotkeys: Vec<(HotKey, String)>,
+    /// Keywords requested via "run <keyword>" on the FIFO (GNOME word shortcuts).
+    word_requests: Arc<Mutex<Vec<String>>>,
+    tap_monitor: TapMonitor,
     command_input: String,
     is_visible: Arc<AtomicBool>,
     text_edit_rect: Option<egui::Rect>,
     settings: Settings,
     settings_window: SettingsWindow,
     was_focused: bool,
-    _tray: tray_icon::TrayIcon,
+    tray: tray_icon::TrayIcon,
     toggle_signal: Arc<AtomicBool>,
     tray_action: Arc<AtomicU8>,
+    /// Profile picked in the tray menu; "" for none.
+    tray_profile: Arc<Mutex<String>>,
     x11_window_id: Arc<AtomicU32>,
     x11_search_attempts: u32,
     dragging: bool,
@@ -34,6 +58,69 @@ pub struct LauncherApp {
     last_known_pos: Option<(f32, f32)>,
     last_pos_query: std::time::Instant,
     needs_initial_move: bool,
+    config_changed: Arc<AtomicBool>,
+    /// Why the last external edit of config.json wasn't applied.
+    config_error: Option<String>,
+    /// Feedback from a built-in command, shown in the empty command bar.
+    notice: Option<String>,
+    /// Highlighted entry of the "#tag" list.
+    tag_selection: usize,
+    /// How many rows under the command bar (the "#tag" list or a
+    /// description) the window was last sized for.
+    extra_rows: usize,
+}
+
+/// Watch the config directory with inotify and raise `signal` whenever
+/// config.json or config.toml is written or replaced. The directory is watched rather than
+/// the file because atomic saves (ours and most editors') swap the inode.
+fn start_config_watcher(ctx: egui::Context, signal: Arc<AtomicBool>) {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = Settings::config_path();
+    let Some(dir) = path.parent() else {
+        return;
+    };
+    let _ = std::fs::create_dir_all(dir);
+    let c_dir = std::ffi::CString::new(dir.as_os_str().as_bytes()).unwrap();
+    // Both, so converting to TOML while running switches over
+    let names: Vec<Vec<u8>> = [Settings::json_config_path(), Settings::toml_config_path()]
+        .iter()
+        .filter_map(|p| p.file_name().map(|n| n.as_bytes().to_vec()))
+        .collect();
+
+    let fd = unsafe { libc::inotify_init1(libc::IN_CLOEXEC) };
+    if fd < 0 || unsafe { libc::inotify_add_watch(fd, c_dir.as_ptr(), libc::IN_CLOSE_WRITE | libc::IN_MOVED_TO) } < 0 {
+        eprintln!("[SlickRun] Failed to watch {:?}: errno={}", dir, unsafe { *libc::__errno_location() });
+        return;
+    }
+    eprintln!("[SlickRun] Watching {:?} for changes", dir);
+
+    std::thread::spawn(move || {
+        let header = std::mem::size_of::<libc::inotify_event>();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+            if n <= 0 {
+                eprintln!("[SlickRun] Config watcher stopped");
+                return;
+            }
+            let mut offset = 0;
+            let mut changed = false;
+            while offset + header <= n as usize {
+                let event: libc::inotify_event =
+                    unsafe { std::ptr::read_unaligned(buf.as_ptr().add(offset) as *const _) };
+                let name_end = offset + header + event.len as usize;
+                // The name is NUL-padded to the event's length
+                let event_name = buf[offset + header..name_end].split(|&b| b == 0).next();
+                changed |= event_name.is_some_and(|n| names.iter().any(|name| name == n));
+                offset = name_end;
+            }
+            if changed {
+                signal.store(true, Ordering::SeqCst);
+                ctx.request_repaint();
+            }
+        }
+    });
 }
 
 fn toggle_pipe_path() -> std::path::PathBuf {
@@ -44,9 +131,11 @@ fn toggle_pipe_path() -> std::path::PathBuf {
     }
 }
 
+/// Listen on the FIFO. "t" toggles the launcher; "run <keyword>" runs a magic word.
 fn start_toggle_pipe_listener(
     ctx: egui::Context,
     signal: Arc<AtomicBool>,
+    word_requests: Arc<Mutex<Vec<String>>>,
 ) {
     let pipe_path = toggle_pipe_path();
     let _ = std::fs::remove_file(&pipe_path);
@@ -65,7 +154,13 @@ fn start_toggle_pipe_listener(
         match std::fs::read_to_string(&pipe_path) {
             Ok(data) => {
                 eprintln!("[SlickRun] FIFO received: {:?}", data.trim());
-                signal.store(true, Ordering::SeqCst);
+                for line in data.lines().map(str::trim).filter(|l| !l.is_empty()) {
+                    if let Some(keyword) = line.strip_prefix("run ") {
+                        word_requests.lock().unwrap().push(keyword.trim().to_string());
+                    } else {
+                        signal.store(true, Ordering::SeqCst);
+                    }
+                }
                 ctx.request_repaint();
             }
             Err(e) => {
@@ -133,132 +228,7 @@ fn find_own_x11_window_inner() -> Result<Option<u32>, Box<dyn std::error::Error>
     Ok(Some(client_window))
 }
 
-/// Activate an X11 window by its stored ID.
-/// Maps the window (un-minimizes) and sends _NET_ACTIVE_WINDOW to the WM.
-fn activate_x11_window_by_id(window_id: u32) {
-    if let Err(e) = activate_x11_window_by_id_inner(window_id) {
-        eprintln!("[SlickRun] X11 activation by ID failed: {e}");
-    }
-}
-
-fn activate_x11_window_by_id_inner(window_id: u32) -> Result<(), Box<dyn std::error::Error>> {
-    use x11rb::connection::Connection;
-    use x11rb::protocol::xproto::*;
-
-    let (conn, screen_num) = x11rb::connect(None)?;
-    let screen = &conn.setup().roots[screen_num];
-    let root = screen.root;
-
-    // Map the window — un-minimizes at X11 level. Harmless if already mapped.
-    conn.map_window(window_id)?;
-
-    // Raise the window
-    let values = ConfigureWindowAux::new().stack_mode(StackMode::ABOVE);
-    conn.configure_window(window_id, &values)?;
-
-    // Send _NET_ACTIVE_WINDOW to the window manager for proper focus/raise
-    let net_active = conn.intern_atom(false, b"_NET_ACTIVE_WINDOW")?.reply()?.atom;
-    let data = ClientMessageData::from([
-        1u32, // source indication: 1 = application
-        0,    // timestamp
-        0,    // requestor's currently active window
-        0, 0,
-    ]);
-    let event = ClientMessageEvent {
-        response_type: CLIENT_MESSAGE_EVENT,
-        format: 32,
-        sequence: 0,
-        window: window_id,
-        type_: net_active,
-        data,
-    };
-    let mask = EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY;
-    conn.send_event(false, root, mask, event)?;
-
-    // Also directly set input focus as a fallback
-    conn.set_input_focus(InputFocus::PARENT, window_id, x11rb::CURRENT_TIME)?;
-
-    conn.flush()?;
-    eprintln!("[SlickRun] Activated window 0x{:x} (map+raise+activate+focus)", window_id);
-    Ok(())
-}
-
-/// Query Mutter for the SlickRun window position via the GNOME Shell extension.
-fn get_mutter_window_position() -> Option<(i32, i32)> {
-    let output = std::process::Command::new("gdbus")
-        .args([
-            "call", "--session",
-            "--dest", "org.gnome.Shell",
-            "--object-path", "/com/slickrun/Toggle",
-            "--method", "com.slickrun.Toggle.GetPosition",
-        ])
-        .output()
-        .ok()?;
-    if !output.status.success() {
-        return None;
-    }
-    // Output format: "(x, y)\n"
-    let s = String::from_utf8_lossy(&output.stdout);
-    let s = s.trim().trim_start_matches('(').trim_end_matches(')');
-    let mut parts = s.split(',');
-    let x: i32 = parts.next()?.trim().parse().ok()?;
-    let y: i32 = parts.next()?.trim().parse().ok()?;
-    if x >= 0 && y >= 0 { Some((x, y)) } else { None }
-}
-
-/// Move the SlickRun window via the GNOME Shell extension (Mutter level).
-/// This is the only way to position a window on GNOME Wayland.
-fn move_window_via_mutter(x: i32, y: i32) {
-    let _ = std::process::Command::new("gdbus")
-        .args([
-            "call", "--session",
-            "--dest", "org.gnome.Shell",
-            "--object-path", "/com/slickrun/Toggle",
-            "--method", "com.slickrun.Toggle.MoveWindow",
-            &x.to_string(),
-            &y.to_string(),
-        ])
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
-        .spawn();
-    eprintln!("[SlickRun] Moving window via Mutter to ({}, {})", x, y);
-}
-
-/// Set _NET_WM_STATE_ABOVE on an X11 window for always-on-top.
-fn set_x11_always_on_top(window_id: u32, enable: bool) {
-    if let Err(e) = set_x11_always_on_top_inner(window_id, enable) {
-        eprintln!("[SlickRun] X11 always-on-top failed: {e}");
-    }
-}
-
-fn set_x11_always_on_top_inner(window_id: u32, enable: bool) -> Result<(), Box<dyn std::error::Error>> {
-    use x11rb::connection::Connection;
-    use x11rb::protocol::xproto::*;
-
-    let (conn, screen_num) = x11rb::connect(None)?;
-    let screen = &conn.setup().roots[screen_num];
-    let root = screen.root;
-
-    let net_wm_state = conn.intern_atom(false, b"_NET_WM_STATE")?.reply()?.atom;
-    let net_wm_state_above = conn.intern_atom(false, b"_NET_WM_STATE_ABOVE")?.reply()?.atom;
-
-    let action = if enable { 1u32 } else { 0u32 }; // 1 = _NET_WM_STATE_ADD, 0 = _NET_WM_STATE_REMOVE
-    let data = ClientMessageData::from([action, net_wm_state_above, 0, 1, 0]);
-    let event = ClientMessageEvent {
-        response_type: CLIENT_MESSAGE_EVENT,
-        format: 32,
-        sequence: 0,
-        window: window_id,
-        type_: net_wm_state,
-        data,
-    };
-    let mask = EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY;
-    conn.send_event(false, root, mask, event)?;
-    conn.flush()?;
-    Ok(())
-}
-
-/// Convert HotkeyConfig to GNOME keybinding format, e.g. "<Control>q"
+/// Convert HotkeyConfig to GNOME keybinding format, e.g. "<Control>q" or "<Super>XF86AudioPlay"
 fn hotkey_to_gnome_binding(hotkey: &HotkeyConfig) -> String {
     let mut s = String::new();
     if hotkey.super_key {
@@ -273,19 +243,15 @@ fn hotkey_to_gnome_binding(hotkey: &HotkeyConfig) -> String {
     if hotkey.alt {
         s.push_str("<Alt>");
     }
-    let key = &hotkey.key;
-    if key.len() == 1 {
-        s.push_str(&key.to_lowercase());
-    } else {
-        s.push_str(key);
-    }
+    s.push_str(keys::keysym(&hotkey.key));
     s
 }
 
 /// Install a minimal GNOME Shell extension that exposes a D-Bus method
 /// to activate (focus) the SlickRun window at Mutter level.
 /// This is the ONLY reliable way to focus a window on GNOME Wayland.
-fn install_gnome_shell_extension() {
+/// Returns whether the extension is already active in the running shell.
+fn install_gnome_shell_extension() -> bool {
     let ext_dir = dirs::data_dir()
         .unwrap_or_else(|| std::path::PathBuf::from("~/.local/share"))
         .join("gnome-shell/extensions/slickrun-toggle@slickrun");
@@ -411,10 +377,12 @@ export default class SlickRunToggleExtension {
     match check {
         Ok(o) if o.status.success() => {
             eprintln!("[SlickRun] Extension D-Bus interface is active");
+            true
         }
         _ => {
             eprintln!("[SlickRun] *** FIRST RUN: Please log out and back in to activate the GNOME Shell extension ***");
             eprintln!("[SlickRun] *** After re-login, Ctrl+Q will properly focus the window ***");
+            false
         }
     }
 }
@@ -441,50 +409,14 @@ fn register_gnome_shortcut(hotkey: &HotkeyConfig) {
         pipe_path.display()
     );
 
-    let slickrun_path =
-        "/org/gnome/settings-daemon/plugins/media-keys/custom-keybindings/slickrun/";
+    let slickrun_path = format!("{}slickrun/", GNOME_CUSTOM_KEYBINDINGS_DIR);
 
-    let output = std::process::Command::new("gsettings")
-        .args([
-            "get",
-            "org.gnome.settings-daemon.plugins.media-keys",
-            "custom-keybindings",
-        ])
-        .output();
-
-    let current = match output {
-        Ok(o) => String::from_utf8_lossy(&o.stdout).trim().to_string(),
-        Err(_) => return,
+    let Some(mut paths) = gnome_custom_keybinding_paths() else {
+        return;
     };
-
-    if !current.contains("slickrun") {
-        let mut paths: Vec<String> = if current == "@as []" || current.is_empty() {
-            vec![]
-        } else {
-            current
-                .trim_start_matches('[')
-                .trim_end_matches(']')
-                .split(',')
-                .map(|s| s.trim().trim_matches('\'').to_string())
-                .filter(|s| !s.is_empty())
-                .collect()
-        };
-        paths.push(slickrun_path.to_string());
-
-        let paths_str = paths
-            .iter()
-            .map(|p| format!("'{}'", p))
-            .collect::<Vec<_>>()
-            .join(", ");
-
-        let _ = std::process::Command::new("gsettings")
-            .args([
-                "set",
-                "org.gnome.settings-daemon.plugins.media-keys",
-                "custom-keybindings",
-                &format!("[{}]", paths_str),
-            ])
-            .output();
+    if !paths.contains(&slickrun_path) {
+        paths.push(slickrun_path.clone());
+        set_gnome_custom_keybinding_paths(&paths);
     }
 
     let schema_path = format!(
@@ -505,29 +437,186 @@ fn register_gnome_shortcut(hotkey: &HotkeyConfig) {
     eprintln!("[SlickRun] Registered GNOME shortcut: {} -> D-Bus + FIFO", binding);
 }
 
+const GNOME_CUSTOM_KEYBINDINGS_DIR: &str =
+    "/org/gnome/settings-daemon/plugins/media-keys/custom-keybindings/";
+
+/// Current list of GNOME custom keybinding paths, or None if gsettings is unavailable.
+pub fn gnome_custom_keybinding_paths() -> Option<Vec<String>> {
+    let output = std::process::Command::new("gsettings")
+        .args([
+            "get",
+            "org.gnome.settings-daemon.plugins.media-keys",
+            "custom-keybindings",
+        ])
+        .output()
+        .ok()?;
+    let current = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if current == "@as []" || current.is_empty() {
+        return Some(vec![]);
+    }
+    Some(
+        current
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .map(|s| s.trim().trim_matches('\'').to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+    )
+}
+
+fn set_gnome_custom_keybinding_paths(paths: &[String]) {
+    let paths_str = paths
+        .iter()
+        .map(|p| format!("'{}'", p))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let _ = std::process::Command::new("gsettings")
+        .args([
+            "set",
+            "org.gnome.settings-daemon.plugins.media-keys",
+            "custom-keybindings",
+            &format!("[{}]", paths_str),
+        ])
+        .output();
+}
+
+/// Keywords end up inside a quoted shell command, so only plain ones get a GNOME shortcut.
+fn is_shell_safe_keyword(keyword: &str) -> bool {
+    !keyword.is_empty()
+        && keyword
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_.+@#".contains(c))
+}
+
+/// Register a GNOME custom shortcut for every magic word with its own hotkey.
+/// Each writes "run <keyword>" to the FIFO. Previous slickrun-word-N entries
+/// are replaced wholesale so removed hotkeys don't linger.
+fn register_gnome_word_shortcuts(words: &[commands::MagicWord], toggle: &HotkeyConfig) {
+    let Some(mut paths) = gnome_custom_keybinding_paths() else {
+        return;
+    };
+    paths.retain(|p| !p.contains("/slickrun-word-"));
+
+    let pipe_path = toggle_pipe_path();
+    let mut count = 0;
+    for mw in words {
+        let Some(hotkey) = &mw.hotkey else {
+            continue;
+        };
+        if hotkey == toggle {
+            continue;
+        }
+        if !is_shell_safe_keyword(&mw.keyword) {
+            eprintln!("[SlickRun] Skipping GNOME shortcut for {:?}: keyword has special characters", mw.keyword);
+            continue;
+        }
+        let path = format!("{}slickrun-word-{}/", GNOME_CUSTOM_KEYBINDINGS_DIR, count);
+        count += 1;
+        let schema_path = format!(
+            "org.gnome.settings-daemon.plugins.media-keys.custom-keybinding:{}",
+            path
+        );
+        let command = format!("bash -c 'echo run {} > {}'", mw.keyword, pipe_path.display());
+        let _ = std::process::Command::new("gsettings")
+            .args(["set", &schema_path, "name", &format!("SlickRun: {}", mw.keyword)])
+            .output();
+        let _ = std::process::Command::new("gsettings")
+            .args(["set", &schema_path, "command", &command])
+            .output();
+        let _ = std::process::Command::new("gsettings")
+            .args(["set", &schema_path, "binding", &hotkey_to_gnome_binding(hotkey)])
+            .output();
+        paths.push(path);
+    }
+
+    set_gnome_custom_keybinding_paths(&paths);
+    eprintln!("[SlickRun] Registered {} GNOME magic word shortcut(s)", count);
+}
+
+/// Grab the toggle hotkey on X11. Returns a user-facing error when another
+/// application already holds the grab — only on desktops where our grab is what
+/// fires the hotkey; GNOME/KDE/wlroots bind it themselves (and often hold the grab).
+fn register_x11_toggle(
+    manager: Option<&GlobalHotKeyManager>,
+    hotkey: &HotkeyConfig,
+    desktop: Desktop,
+) -> Option<String> {
+    let hk = hotkey.to_hotkey()?;
+    let Some(manager) = manager else {
+        return (desktop == Desktop::Other)
+            .then(|| "No X server to grab global hotkeys on, so the hotkey won't open SlickRun.".to_string());
+    };
+    let message = match manager.register(hk) {
+        Ok(()) => return None,
+        Err(global_hotkey::Error::AlreadyRegistered(_)) => format!(
+            "{} is already grabbed by another application, so it won't open SlickRun.",
+            hotkey.display_string()
+        ),
+        Err(e) => format!("Could not register {}: {e}", hotkey.display_string()),
+    };
+    eprintln!("[SlickRun] X11 hotkey: {}", message);
+    (desktop == Desktop::Other).then_some(message)
+}
+
 impl LauncherApp {
-    pub fn new(cc: &eframe::CreationContext<'_>, tray: tray_icon::TrayIcon) -> Self {
+    /// Set up hotkeys, listeners and the tray for the window `egui_ctx`
+    /// draws into: eframe's, or the layer surface's on wlroots.
+    pub fn new(
+        egui_ctx: &egui::Context,
+        tray: tray_icon::TrayIcon,
+        desktop: Desktop,
+    ) -> Self {
         let settings = Settings::load();
-        let manager = GlobalHotKeyManager::new().unwrap();
+        let manager = GlobalHotKeyManager::new()
+            .inspect_err(|e| eprintln!("[SlickRun] X11 hotkeys unavailable: {e}"))
+            .ok();
 
         let hotkey = settings.hotkey.to_hotkey().expect("Invalid hotkey config");
-        match manager.register(hotkey) {
-            Ok(()) | Err(global_hotkey::Error::AlreadyRegistered(_)) => {}
-            Err(e) => eprintln!("[SlickRun] Failed to register X11 hotkey: {e}"),
-        }
-
-        // Install GNOME Shell extension for Mutter-level window activation
-        install_gnome_shell_extension();
+        let toggle_hotkey_error = register_x11_toggle(manager.as_ref(), &settings.hotkey, desktop);
+
+        eprintln!("[SlickRun] Desktop: {:?}", desktop);
+        let mut gnome_extension_active = false;
+        match desktop {
+            Desktop::Kde => {
+                // Register kglobalaccel shortcut (works on Plasma Wayland)
+                kwin::register_shortcut(&SystemRunner, &settings.hotkey, &toggle_pipe_path());
+            }
+            Desktop::Wlroots(compositor) => {
+                wlroots::register_shortcut(&SystemRunner, compositor, &settings.hotkey, &toggle_pipe_path());
+            }
+            Desktop::Gnome | Desktop::Other => {
+                // Install GNOME Shell extension for Mutter-level window activation
+                gnome_extension_active = install_gnome_shell_extension();
 
-        // Register GNOME custom shortcut (works on Wayland)
-        register_gnome_shortcut(&settings.hotkey);
+                // Register GNOME custom shortcut (works on Wayland)
+                register_gnome_shortcut(&settings.hotkey);
+            }
+        }
 
         // FIFO pipe for toggle
-        let needs_initial_move = settings.window_x.is_some() && settings.window_y.is_some();
+        let needs_initial_move = true;
         let toggle_signal = Arc::new(AtomicBool::new(false));
+        let word_requests = Arc::new(Mutex::new(Vec::new()));
         let is_visible = Arc::new(AtomicBool::new(true));
         let x11_window_id = Arc::new(AtomicU32::new(0));
-        start_toggle_pipe_listener(cc.egui_ctx.clone(), toggle_signal.clone());
+        let window = window_backend::select(desktop, gnome_extension_active, x11_window_id.clone());
+        eprintln!("[SlickRun] Window backend: {}", window.name());
+        start_toggle_pipe_listener(
+            egui_ctx.clone(),
+            toggle_signal.clone(),
+            word_requests.clone(),
+        );
+
+        let config_changed = Arc::new(AtomicBool::new(false));
+        start_config_watcher(egui_ctx.clone(), config_changed.clone());
+
+        // Modifier taps feed the same toggle signal as the FIFO
+        let mut tap_monitor = TapMonitor::new(egui_ctx.clone(), toggle_signal.clone());
+        if !desktop.is_wlroots() {
+            tap_monitor.set(settings.modifier_tap);
+        }
 
         // Shared window position — updated in update(), read by tray handler on quit
         let shared_pos_x = Arc::new(AtomicU32::new(0));
@@ -536,7 +625,9 @@ impl LauncherApp {
         // Tray menu events via set_event_handler
         let tray_action = Arc::new(AtomicU8::new(TRAY_NONE));
         let tray_action_for_handler = tray_action.clone();
-        let ctx_for_tray = cc.egui_ctx.clone();
+        let tray_profile = Arc::new(Mutex::new(String::new()));
+        let tray_profile_for_handler = tray_profile.clone();
+        let ctx_for_tray = egui_ctx.clone();
         let x11_id_for_tray = x11_window_id.clone();
         let pipe_for_tray = toggle_pipe_path();
         let pos_x_for_tray = shared_pos_x.clone();
@@ -551,9 +642,10 @@ impl LauncherApp {
                     let y = f32::from_bits(pos_y_for_tray.load(Ordering::SeqCst));
                     if x != 0.0 || y != 0.0 {
                         let mut s = Settings::load();
-                        s.window_x = Some(x);
-                        s.window_y = Some(y);
-                        s.save();
+                        s.remember_position(x, y);
+                        if let Err(e) = s.save() {
+                            eprintln!("[SlickRun] Failed to save settings: {e}");
+                        }
                     }
                     let _ = std::fs::remove_file(&pipe_for_tray);
                     std::process::exit(0);
@@ -562,12 +654,18 @@ impl LauncherApp {
                     let action = match other {
                         "show_hide" => TRAY_TOGGLE,
                         "settings" => TRAY_SETTINGS,
-                        _ => TRAY_NONE,
+                        _ => match other.strip_prefix("profile:") {
+                            Some(name) => {
+                                *tray_profile_for_handler.lock().unwrap() = name.to_string();
+                                TRAY_PROFILE
+                            }
+                            None => TRAY_NONE,
+                        },
                     };
                     // Unminimize window via X11 so update() runs
                     let wid = x11_id_for_tray.load(Ordering::SeqCst);
                     if wid != 0 {
-                        activate_x11_window_by_id(wid);
+                        window_backend::activate_x11_window_by_id(wid);
                     }
                     tray_action_for_handler.store(action, Ordering::SeqCst);
                     ctx_for_tray.request_repaint();
@@ -586,23 +684,34 @@ impl LauncherApp {
 
         eprintln!("=== SlickRun ===");
         eprintln!("Hotkey: {}", settings.hotkey.display_string());
-        eprintln!("Built-in commands: setup, quit");
+        eprintln!("Built-in commands: setup, quit, profile <name>");
         eprintln!("================");
 
-        let settings_window = SettingsWindow::new(&settings);
+        let mut settings_window = SettingsWindow::new(&settings);
+        settings_window.hotkey_error = toggle_hotkey_error;
+        if settings.load_error.is_some() {
+            // Open straight into settings so the error and restore options are seen
+            settings_window.open(&settings);
+        }
 
-        Self {
+        let mut app = Self {
+            desktop,
+            window,
             hotkey_manager: manager,
             toggle_hotkey_id: hotkey.id(),
+            word_hotkeys: Vec::new(),
+            word_requests,
+            tap_monitor,
             command_input: String::new(),
             is_visible,
             text_edit_rect: None,
             settings,
             settings_window,
             was_focused: false,
-            _tray: tray,
+            tray,
             toggle_signal,
             tray_action,
+            tray_profile,
             x11_window_id,
             x11_search_attempts: 0,
             dragging: false,
@@ -613,7 +722,18 @@ impl LauncherApp {
             last_known_pos: None,
             last_pos_query: std::time::Instant::now(),
             needs_initial_move,
-        }
+            config_changed,
+            config_error: None,
+            notice: None,
+            tag_selection: 0,
+            extra_rows: 0,
+        };
+        app.register_word_hotkeys();
+        app
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
     }
 
     fn toggle_visibility(&mut self, ctx: &egui::Context) {
@@ -625,17 +745,18 @@ impl LauncherApp {
     }
 
     fn save_position(&mut self, _ctx: &egui::Context) {
-        // Try cached position first, fall back to querying Mutter directly
-        let pos = self.last_known_pos.or_else(|| {
-            get_mutter_window_position().map(|(x, y)| (x as f32, y as f32))
-        });
+        // Try cached position first, fall back to querying the compositor directly
+        let pos = self
+            .last_known_pos
+            .or_else(|| self.window.position().map(|(x, y)| (x as f32, y as f32)));
         if let Some((x, y)) = pos {
             eprintln!("[SlickRun] Saving position: ({}, {})", x, y);
-            self.settings.window_x = Some(x);
-            self.settings.window_y = Some(y);
+            self.settings.remember_position(x, y);
             self.shared_pos_x.store(x.to_bits(), Ordering::SeqCst);
             self.shared_pos_y.store(y.to_bits(), Ordering::SeqCst);
-            self.settings.save();
+            if let Err(e) = self.settings.save() {
+                eprintln!("[SlickRun] Failed to save position: {e}");
+            }
         } else {
             eprintln!("[SlickRun] save_position: no known position yet");
         }
@@ -653,85 +774,124 @@ impl LauncherApp {
         self.save_position(ctx);
         self.is_visible.store(false, Ordering::SeqCst);
         ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+        self.window.hide();
     }
 
     fn show(&mut self, ctx: &egui::Context) {
         self.is_visible.store(true, Ordering::SeqCst);
         // Unminimize
         ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
-        // Restore saved position, or center on screen
+        // Place according to the monitor policy: remembered position for this
+        // monitor layout, transplanted onto the target monitor, or centered
+        let fallback = ctx
+            .input(|i| i.viewport().monitor_size)
+            .unwrap_or(egui::vec2(1200.0, 900.0));
         let pos = if self.settings_window.open {
-            let win_w = 650.0;
-            let win_h = 550.0;
-            ctx.input(|i| i.viewport().monitor_size)
-                .map(|ms| egui::pos2((ms.x - win_w) / 2.0, (ms.y - win_h) / 2.0))
-                .unwrap_or(egui::pos2(400.0, 300.0))
-        } else if let (Some(x), Some(y)) = (self.settings.window_x, self.settings.window_y) {
-            egui::pos2(x, y)
+            let size = (650.0, 550.0);
+            monitors::center_window(&self.settings, size)
+                .map(|(x, y)| egui::pos2(x, y))
+                .unwrap_or(egui::pos2((fallback.x - size.0) / 2.0, (fallback.y - size.1) / 2.0))
         } else {
-            let win_w = self.settings.window_width;
-            let win_h = self.settings.window_height;
-            ctx.input(|i| i.viewport().monitor_size)
-                .map(|ms| egui::pos2((ms.x - win_w) / 2.0, (ms.y - win_h) / 2.0))
-                .unwrap_or(egui::pos2(400.0, 300.0))
+            let size = self.settings.appearance().size();
+            monitors::place_window(&self.settings, size)
+                .map(|(x, y)| egui::pos2(x, y))
+                .unwrap_or(egui::pos2((fallback.x - size.0) / 2.0, (fallback.y - size.1) / 2.0))
         };
         ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(pos));
         ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
 
-        // Move via Mutter (OuterPosition doesn't work on Wayland)
-        move_window_via_mutter(pos.x as i32, pos.y as i32);
+        // Move via the compositor (OuterPosition doesn't work on Wayland)
+        self.window.move_to(pos.x as i32, pos.y as i32);
 
         // Re-assert always-on-top after moving back on-screen
         if self.settings.stay_on_top {
             ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(
                 egui::WindowLevel::AlwaysOnTop,
             ));
-            let wid = self.x11_window_id.load(Ordering::SeqCst);
-            if wid != 0 {
-                set_x11_always_on_top(wid, true);
-            }
+            self.window.set_always_on_top(true);
         }
 
         // Reset focus tracking so auto-hide doesn't fire immediately
         // (Mutter focus takes a frame or two to propagate)
         self.was_focused = false;
 
-        // Request Mutter-level focus via GNOME Shell extension.
-        // This is the ONLY way to get proper focus on GNOME Wayland.
-        // When triggered from the keyboard shortcut, the shortcut command
-        // already called Activate before the FIFO. This call covers
-        // other show paths (tray menu, etc.) and is harmless if called twice.
-        let _ = std::process::Command::new("gdbus")
-            .args([
-                "call", "--session",
-                "--dest", "org.gnome.Shell",
-                "--object-path", "/com/slickrun/Toggle",
-                "--method", "com.slickrun.Toggle.Activate",
-            ])
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
-            .spawn();
-
-        // X11 activation as fallback (works on native X11, not on Wayland)
-        let wid = self.x11_window_id.load(Ordering::SeqCst);
-        if wid != 0 {
-            activate_x11_window_by_id(wid);
-        }
+        // Compositor-level focus — the only reliable way on Wayland sessions.
+        self.window.activate();
     }
 
     fn re_register_hotkey(&mut self) {
         if let Some(new_hk) = self.settings.hotkey.to_hotkey() {
-            match self.hotkey_manager.register(new_hk) {
-                Ok(()) | Err(global_hotkey::Error::AlreadyRegistered(_)) => {
-                    self.toggle_hotkey_id = new_hk.id();
-                }
-                Err(e) => eprintln!("Failed to register hotkey: {e}"),
+            self.toggle_hotkey_id = new_hk.id();
+        }
+        self.settings_window.hotkey_error =
+            register_x11_toggle(self.hotkey_manager.as_ref(), &self.settings.hotkey, self.desktop);
+        match self.desktop {
+            Desktop::Kde => kwin::register_shortcut(&SystemRunner, &self.settings.hotkey, &toggle_pipe_path()),
+            Desktop::Wlroots(compositor) => {
+                wlroots::register_shortcut(&SystemRunner, compositor, &self.settings.hotkey, &toggle_pipe_path())
             }
+            Desktop::Gnome | Desktop::Other => register_gnome_shortcut(&self.settings.hotkey),
         }
-        register_gnome_shortcut(&self.settings.hotkey);
     }
 
-    fn apply_settings(&mut self, new_settings: Settings, ctx: &egui::Context) {
+    /// (Re-)register the per-magic-word hotkeys: X11 grabs everywhere, plus
+    /// GNOME custom shortcuts where the X11 grab can't see keys (Wayland).
+    fn register_word_hotkeys(&mut self) {
+        let old: Vec<HotKey> = self.word_hotkeys.drain(..).map(|(hk, _)| hk).collect();
+        if let Some(manager) = &self.hotkey_manager {
+            let _ = manager.unregister_all(&old);
+        }
+
+        for mw in self.settings.all_words() {
+            let Some(config) = &mw.hotkey else {
+                continue;
+            };
+            if *config == self.settings.hotkey {
+                eprintln!("[SlickRun] Hotkey for {:?} is the toggle hotkey — ignored", mw.keyword);
+                continue;
+            }
+            let Some(hotkey) = config.to_hotkey() else {
+                eprintln!("[SlickRun] Invalid hotkey for {:?}: {}", mw.keyword, config.display_string());
+                continue;
+            };
+            if self.word_hotkeys.iter().any(|(hk, _)| hk.id() == hotkey.id()) {
+                eprintln!("[SlickRun] Hotkey {} for {:?} is already used by another magic word", config.display_string(), mw.keyword);
+                continue;
+            }
+            match self.hotkey_manager.as_ref().map(|manager| manager.register(hotkey)) {
+                Some(Ok(())) | Some(Err(global_hotkey::Error::AlreadyRegistered(_))) | None => {}
+                Some(Err(e)) => eprintln!("[SlickRun] Failed to register hotkey for {:?}: {e}", mw.keyword),
+            }
+            self.word_hotkeys.push((hotkey, mw.keyword.clone()));
+        }
+
+        if matches!(self.desktop, Desktop::Gnome | Desktop::Other) {
+            let words: Vec<commands::MagicWord> = self.settings.all_words().cloned().collect();
+            register_gnome_word_shortcuts(&words, &self.settings.hotkey);
+        }
+    }
+
+    /// Run a magic word triggered by its own hotkey. Words that need $W$
+    /// arguments bring up the launcher with the parameter dialog instead.
+    fn run_word_hotkey(&mut self, keyword: &str, ctx: &egui::Context) {
+        let Some(mw) = self.settings.find_word(keyword).cloned() else {
+            eprintln!("[SlickRun] Hotkey for unknown magic word {:?}", keyword);
+            return;
+        };
+        eprintln!("[SlickRun] Magic word hotkey: {}", mw.keyword);
+        if mw.needs_w_input() {
+            self.show(ctx);
+            self.pending_w_magic_word = Some(mw);
+            self.w_dialog_input.clear();
+        } else {
+            mw.execute("");
+        }
+    }
+
+    /// Switch to `new_settings`, re-registering hotkeys as needed. `save` is
+    /// false when the settings came from config.json itself (live reload), so
+    /// a hand-edited file isn't rewritten.
+    fn apply_settings(&mut self, new_settings: Settings, ctx: &egui::Context, save: bool) {
         let hotkey_changed = {
             let old = &self.settings.hotkey;
             let new = &new_settings.hotkey;
@@ -741,20 +901,35 @@ impl LauncherApp {
                 || old.shift != new.shift
                 || old.alt != new.alt
         };
+        let word_hotkeys_of = |s: &Settings| -> Vec<(String, HotkeyConfig)> {
+            s.all_words()
+                .filter_map(|mw| mw.hotkey.clone().map(|hk| (mw.keyword.clone(), hk)))
+                .collect()
+        };
+        let word_hotkeys_changed =
+            hotkey_changed || word_hotkeys_of(&self.settings) != word_hotkeys_of(&new_settings);
+        let profiles_changed = self.settings.profiles != new_settings.profiles
+            || self.settings.active_profile != new_settings.active_profile;
 
         if hotkey_changed {
-            if let Some(old_hk) = self.settings.hotkey.to_hotkey() {
-                let _ = self.hotkey_manager.unregister(old_hk);
+            if let Some(old_hk) = self.settings.hotkey.to_hotkey()
+                && let Some(manager) = &self.hotkey_manager
+            {
+                let _ = manager.unregister(old_hk);
+            }
+            if let Desktop::Wlroots(compositor) = self.desktop {
+                wlroots::unregister_shortcut(&SystemRunner, compositor, &self.settings.hotkey);
             }
         }
 
         self.settings = new_settings;
-        self.settings.save();
+        self.settings.compile_patterns();
+        if save && let Err(e) = self.settings.save() {
+            eprintln!("[SlickRun] Failed to save settings: {e}");
+            self.settings_window.report_save_error(&e);
+        }
 
-        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::Vec2::new(
-            self.settings.window_width,
-            self.settings.window_height,
-        )));
+        ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(self.settings.appearance().size().into()));
 
         let level = if self.settings.stay_on_top {
             egui::WindowLevel::AlwaysOnTop
@@ -762,14 +937,123 @@ impl LauncherApp {
             egui::WindowLevel::Normal
         };
         ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
+        self.window.set_always_on_top(self.settings.stay_on_top);
+        if !self.desktop.is_wlroots() {
+            self.tap_monitor.set(self.settings.modifier_tap);
+        }
 
         self.settings.update_autostart();
+        if profiles_changed {
+            self.tray.set_menu(Some(Box::new(crate::tray_menu(&self.settings))));
+        }
 
+        // Word hotkeys first: one of the old ones may hold the new toggle combo
+        if word_hotkeys_changed {
+            self.register_word_hotkeys();
+        }
         if hotkey_changed {
             self.re_register_hotkey();
         }
     }
 
+    /// Re-read config.json after it changed on disk. A file that doesn't parse
+    /// or fails validation is reported instead of applied, and saving is
+    /// blocked until it is fixed so a half-finished edit is never overwritten.
+    fn reload_settings(&mut self, ctx: &egui::Context) {
+        if !Settings::config_path().exists() {
+            // Mid-rename, or deleted — keep what we have
+            return;
+        }
+        let mut loaded = Settings::load();
+        // Our own saves land here too; the settings window may save words
+        // with errors, and those must not lock saving
+        let unchanged = loaded.load_error.is_none()
+            && serde_json::to_value(&loaded).ok() == serde_json::to_value(&self.settings).ok();
+        if !unchanged && loaded.load_error.is_none() {
+            let error = validation::validate(&loaded)
+                .into_iter()
+                .find(|issue| issue.severity == Severity::Error);
+            loaded.load_error = error.map(|issue| match issue.word {
+                Some(i) => format!("word {} \"{}\": {}", i + 1, loaded.magic_words[i].keyword, issue.message),
+                None => issue.message,
+            });
+        }
+        if let Some(e) = loaded.load_error {
+            eprintln!("[SlickRun] Not reloading config: {e}");
+            self.settings.load_error = Some(e.clone());
+            self.config_error = Some(e);
+            return;
+        }
+        self.settings.load_error = None;
+        self.config_error = None;
+        if unchanged {
+            return;
+        }
+        eprintln!("[SlickRun] {} changed on disk — reloading", Settings::config_path().display());
+        self.apply_settings(loaded, ctx, false);
+        self.settings_window.reloaded_from_disk(&self.settings);
+    }
+
+    /// Make `name` the active profile ("" for none) and save. An unknown
+    /// name changes nothing.
+    fn switch_profile(&mut self, name: &str, ctx: &egui::Context) -> Result<(), String> {
+        let profile = if name.is_empty() {
+            None
+        } else {
+            let found = self.settings.profiles.iter().find(|p| p.name.eq_ignore_ascii_case(name));
+            let Some(found) = found else {
+                let names: Vec<&str> = self.settings.profiles.iter().map(|p| p.name.as_str()).collect();
+                return Err(format!("No profile \"{}\" (have: {})", name, names.join(", ")));
+            };
+            Some(found.name.clone())
+        };
+        eprintln!("[SlickRun] Switching to profile {:?}", profile);
+        let mut new_settings = self.settings.clone();
+        new_settings.active_profile = profile.clone();
+        // An open settings window would otherwise switch back on save
+        self.settings_window.set_active_profile(profile.clone());
+        self.apply_settings(new_settings, ctx, true);
+        self.notice = Some(match profile {
+            Some(name) => format!("Profile: {}", name),
+            None => "No profile — shared words only".into(),
+        });
+        Ok(())
+    }
+
+    /// Words listed for "#tag [keyword]" input: one of their tags starts with
+    /// `tag`, and one of their names with `keyword` if given.
+    fn tag_search(&self) -> Option<Vec<commands::MagicWord>> {
+        let rest = self.command_input.trim_start().strip_prefix('#')?;
+        let (tag, name) = match rest.split_once(char::is_whitespace) {
+            Some((tag, name)) => (tag, name.trim().to_lowercase()),
+            None => (rest, String::new()),
+        };
+        Some(
+            self.settings
+                .all_words()
+                .filter(|mw| mw.has_tag_prefix(tag))
+                .filter(|mw| {
+                    name.is_empty() || mw.names().any(|n| n.to_lowercase().starts_with(&name))
+                })
+                .take(TAG_LIST_MAX)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Run a word picked from the "#tag" list.
+    fn run_listed_word(&mut self, mw: commands::MagicWord, ctx: &egui::Context) {
+        self.command_input.clear();
+        self.tag_selection = 0;
+        if mw.needs_w_input() {
+            self.pending_w_magic_word = Some(mw);
+            self.w_dialog_input.clear();
+            return;
+        }
+        mw.execute("");
+        self.hide(ctx);
+    }
+
     fn best_autocomplete(&self, input: &str) -> Option<String> {
         if input.is_empty() {
             return None;
@@ -779,9 +1063,10 @@ impl LauncherApp {
             return None;
         }
         let lower = first_word.to_lowercase();
-        let mut names: Vec<String> = vec!["setup".into(), "quit".into()];
-        for mw in &self.settings.magic_words {
-            names.push(mw.keyword.clone());
+        let mut names: Vec<String> = vec!["setup".into(), "quit".into(), "profile".into()];
+        for mw in self.settings.all_words() {
+            names.extend(mw.names().map(str::to_string));
+            names.extend(mw.tags.iter().map(|t| format!("#{}", t)));
         }
         names
             .into_iter()
@@ -794,6 +1079,14 @@ impl LauncherApp {
         if input.is_empty() {
             return;
         }
+        self.notice = None;
+
+        if let Some(tag) = input.strip_prefix('#') {
+            // Only reached when the tag list is empty
+            self.command_input.clear();
+            self.notice = Some(format!("No magic words tagged #{}", tag));
+            return;
+        }
 
         let (first_word, user_args) = match input.find(char::is_whitespace) {
             Some(pos) => (&input[..pos], input[pos..].trim()),
@@ -811,7 +1104,15 @@ impl LauncherApp {
             return;
         }
 
-        if let Some(mw) = commands::find_by_keyword(&self.settings.magic_words, first_word) {
+        if first_word.eq_ignore_ascii_case("profile") && self.settings.find_word(first_word).is_none() {
+            self.command_input.clear();
+            if let Err(e) = self.switch_profile(user_args, ctx) {
+                self.notice = Some(e);
+            }
+            return;
+        }
+
+        if let Some(mw) = self.settings.find_word(first_word) {
             if user_args.is_empty() && mw.needs_w_input() {
                 // $W$ magic word with no args — show input dialog
                 self.pending_w_magic_word = Some(mw.clone());
@@ -825,6 +1126,18 @@ impl LauncherApp {
             return;
         }
 
+        if let Some(mw) = self.settings.match_pattern(&input) {
+            self.command_input.clear();
+            if mw.needs_w_input() {
+                self.pending_w_magic_word = Some(mw);
+                self.w_dialog_input.clear();
+                return;
+            }
+            mw.execute("");
+            self.hide(ctx);
+            return;
+        }
+
         let _ = std::process::Command::new("sh")
             .arg("-c")
             .arg(&input)
@@ -837,26 +1150,37 @@ impl LauncherApp {
 
 impl eframe::App for LauncherApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Store our X11 window ID on the first frames (while we still have focus)
-        if self.x11_window_id.load(Ordering::SeqCst) == 0 && self.x11_search_attempts < 10 {
+        self.run_frame(ctx);
+    }
+}
+
+impl LauncherApp {
+    /// One pass of the UI and of the polling behind it (FIFO, hotkeys, tray).
+    pub fn run_frame(&mut self, ctx: &egui::Context) {
+        // Store our X11 window ID on the first frames (while we still have focus).
+        // On wlroots we are a native Wayland client, so any X11 focus belongs to someone else.
+        if !self.desktop.is_wlroots()
+            && self.x11_window_id.load(Ordering::SeqCst) == 0
+            && self.x11_search_attempts < 10
+        {
             self.x11_search_attempts += 1;
             if let Some(id) = find_own_x11_window() {
                 self.x11_window_id.store(id, Ordering::SeqCst);
                 eprintln!("[SlickRun] Stored X11 window ID: 0x{:x}", id);
                 if self.settings.stay_on_top {
-                    set_x11_always_on_top(id, true);
+                    self.window.set_always_on_top(true);
                 }
             }
         }
 
-        // Track window position via Mutter (only way to get real position on XWayland).
+        // Track window position via the compositor (only way to get real position on XWayland).
         // Query every ~1 second while visible to avoid spawning gdbus too often.
         if self.is_visible.load(Ordering::SeqCst) {
             let should_query = self.last_known_pos.is_none()
                 || self.last_pos_query.elapsed() > std::time::Duration::from_secs(1);
             if should_query {
                 self.last_pos_query = std::time::Instant::now();
-                if let Some((x, y)) = get_mutter_window_position() {
+                if let Some((x, y)) = self.window.position() {
                     self.last_known_pos = Some((x as f32, y as f32));
                     self.shared_pos_x.store((x as f32).to_bits(), Ordering::SeqCst);
                     self.shared_pos_y.store((y as f32).to_bits(), Ordering::SeqCst);
@@ -871,6 +1195,10 @@ impl eframe::App for LauncherApp {
             gtk::main_iteration_do(false);
         }
 
+        if self.config_changed.swap(false, Ordering::SeqCst) {
+            self.reload_settings(ctx);
+        }
+
         // Toggle pipe (FIFO from GNOME keyboard shortcut)
         if self.toggle_signal.swap(false, Ordering::SeqCst) {
             eprintln!("[SlickRun] Toggle signal received via FIFO");
@@ -882,9 +1210,24 @@ impl eframe::App for LauncherApp {
             if event.id() == self.toggle_hotkey_id {
                 eprintln!("[SlickRun] X11 hotkey toggle");
                 self.toggle_visibility(ctx);
+            } else if event.state() == HotKeyState::Pressed {
+                let keyword = self
+                    .word_hotkeys
+                    .iter()
+                    .find(|(hk, _)| hk.id() == event.id())
+                    .map(|(_, kw)| kw.clone());
+                if let Some(keyword) = keyword {
+                    self.run_word_hotkey(&keyword, ctx);
+                }
             }
         }
 
+        // Magic word shortcuts delivered through the FIFO (GNOME Wayland)
+        let requested: Vec<String> = std::mem::take(&mut *self.word_requests.lock().unwrap());
+        for keyword in requested {
+            self.run_word_hotkey(&keyword, ctx);
+        }
+
         // Tray menu events (via set_event_handler → AtomicU8)
         match self.tray_action.swap(TRAY_NONE, Ordering::SeqCst) {
             TRAY_TOGGLE => {
@@ -896,6 +1239,13 @@ impl eframe::App for LauncherApp {
                 self.show(ctx);
                 self.settings_window.open(&self.settings);
             }
+            TRAY_PROFILE => {
+                let name = std::mem::take(&mut *self.tray_profile.lock().unwrap());
+                eprintln!("[SlickRun] Tray: profile {:?}", name);
+                if let Err(e) = self.switch_profile(&name, ctx) {
+                    eprintln!("[SlickRun] {e}");
+                }
+            }
             TRAY_QUIT => {
                 eprintln!("[SlickRun] Tray: quit");
                 self.show(ctx); // unminimize so Close command processes cleanly
@@ -907,22 +1257,14 @@ impl eframe::App for LauncherApp {
         // Always track focus state so transitions are detected correctly
         let focused = ctx.input(|i| i.focused);
 
-        // On first focus, move window to saved position via Mutter (blocking).
+        // On first focus, move window to saved position via the window backend.
         // Must wait until window has focus — move_frame doesn't work on unfocused windows.
         if self.needs_initial_move && focused {
             self.needs_initial_move = false;
-            if let (Some(x), Some(y)) = (self.settings.window_x, self.settings.window_y) {
-                eprintln!("[SlickRun] Initial move to saved position ({}, {})", x, y);
-                let _ = std::process::Command::new("gdbus")
-                    .args([
-                        "call", "--session",
-                        "--dest", "org.gnome.Shell",
-                        "--object-path", "/com/slickrun/Toggle",
-                        "--method", "com.slickrun.Toggle.MoveWindow",
-                        &(x as i32).to_string(),
-                        &(y as i32).to_string(),
-                    ])
-                    .output(); // blocking — wait for move to complete
+            let size = self.settings.appearance().size();
+            if let Some((x, y)) = monitors::place_window(&self.settings, size) {
+                eprintln!("[SlickRun] Initial move to ({}, {})", x, y);
+                self.window.move_to(x as i32, y as i32);
             }
         }
 
@@ -936,11 +1278,16 @@ impl eframe::App for LauncherApp {
         }
 
         // Auto-hide on focus loss (suppressed while dragging or $W$ dialog open)
-        if self.settings.hide_on_inactive && self.is_visible.load(Ordering::SeqCst) && !self.settings_window.open && !self.dragging && self.pending_w_magic_word.is_none() {
-            if self.was_focused && !focused {
-                eprintln!("[SlickRun] Focus lost — auto-hiding");
-                self.hide(ctx);
-            }
+        if self.settings.hide_on_inactive
+            && self.is_visible.load(Ordering::SeqCst)
+            && !self.settings_window.open
+            && !self.dragging
+            && self.pending_w_magic_word.is_none()
+            && self.was_focused
+            && !focused
+        {
+            eprintln!("[SlickRun] Focus lost — auto-hiding");
+            self.hide(ctx);
         }
 
         if focused != self.was_focused {
@@ -950,15 +1297,16 @@ impl eframe::App for LauncherApp {
 
         // Settings mode: resize window and render settings UI inline
         if self.settings_window.open {
-            // ESC closes settings
-            if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
-                self.settings_window.open = false;
-                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::Vec2::new(
-                    self.settings.window_width,
-                    self.settings.window_height,
-                )));
-                ctx.request_repaint_after(std::time::Duration::from_millis(200));
-                return;
+            // ESC closes settings (unless it is cancelling a hotkey recording)
+            if !self.settings_window.is_recording_hotkey()
+                && ctx.input(|i| i.key_pressed(egui::Key::Escape))
+            {
+                self.settings_window.request_close();
+                if !self.settings_window.open {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(self.settings.appearance().size().into()));
+                    ctx.request_repaint_after(std::time::Duration::from_millis(200));
+                    return;
+                }
             }
 
             // Ensure window is large enough for settings
@@ -967,23 +1315,19 @@ impl eframe::App for LauncherApp {
 
             let saved = self.settings_window.show_in_viewport(ctx);
             if let Some(new_settings) = saved {
-                self.apply_settings(new_settings, ctx);
+                self.apply_settings(new_settings, ctx, true);
             }
             if !self.settings_window.open {
                 // Settings just closed — restore command bar size
-                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::Vec2::new(
-                    self.settings.window_width,
-                    self.settings.window_height,
-                )));
+                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(self.settings.appearance().size().into()));
             }
 
             // Drag support in settings mode — only the very top edge (above tab bar)
-            if ctx.input(|i| i.pointer.primary_pressed()) {
-                if let Some(pos) = ctx.input(|i| i.pointer.latest_pos()) {
-                    if pos.y < 4.0 {
-                        ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
-                    }
-                }
+            if ctx.input(|i| i.pointer.primary_pressed())
+                && let Some(pos) = ctx.input(|i| i.pointer.latest_pos())
+                && pos.y < 4.0
+            {
+                ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
             }
 
             ctx.request_repaint_after(std::time::Duration::from_millis(200));
@@ -993,9 +1337,9 @@ impl eframe::App for LauncherApp {
         // $W$ parameter input dialog
         if self.pending_w_magic_word.is_some() {
             // Expand window to fit the label + input (scales with font size)
-            let w_dialog_height = 12.0 + 14.0 + 6.0 + self.settings.font_size + 16.0 + 12.0 + 20.0;
+            let w_dialog_height = 12.0 + 14.0 + 6.0 + self.settings.appearance().font_size + 16.0 + 12.0 + 20.0;
             ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::Vec2::new(
-                self.settings.window_width,
+                self.settings.appearance().window_width,
                 w_dialog_height,
             )));
 
@@ -1028,7 +1372,7 @@ impl eframe::App for LauncherApp {
                     let response = ui.add(
                         egui::TextEdit::singleline(&mut self.w_dialog_input)
                             .id(w_input_id)
-                            .font(egui::FontId::monospace(self.settings.font_size))
+                            .font(egui::FontId::monospace(self.settings.appearance().font_size))
                             .desired_width(f32::INFINITY)
                             .hint_text("Type or paste text, then press Enter"),
                     );
@@ -1040,20 +1384,14 @@ impl eframe::App for LauncherApp {
                 let args = self.w_dialog_input.clone();
                 self.w_dialog_input.clear();
                 // Restore original window size before hiding
-                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::Vec2::new(
-                    self.settings.window_width,
-                    self.settings.window_height,
-                )));
+                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(self.settings.appearance().size().into()));
                 mw.execute(&args);
                 self.hide(ctx);
             } else if w_escape {
                 self.pending_w_magic_word = None;
                 self.w_dialog_input.clear();
                 // Restore original window size
-                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::Vec2::new(
-                    self.settings.window_width,
-                    self.settings.window_height,
-                )));
+                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(self.settings.appearance().size().into()));
             }
 
             ctx.request_repaint_after(std::time::Duration::from_millis(200));
@@ -1087,7 +1425,40 @@ impl eframe::App for LauncherApp {
             None
         };
 
-        let font_size = self.settings.font_size;
+        let font_size = self.settings.appearance().font_size;
+
+        // Under the bar: the words a "#tag" lists, or the description of the
+        // suggested word; grow the window to fit
+        let tag_list = self.tag_search();
+        let description = match tag_list {
+            Some(_) => None,
+            None => self
+                .best_autocomplete(&self.command_input)
+                .and_then(|name| self.settings.find_word(&name))
+                .and_then(|mw| mw.description.clone()),
+        };
+        let row_height = font_size + 8.0;
+        let rows = match &tag_list {
+            Some(list) => list.len().max(1),
+            None => usize::from(description.is_some()),
+        };
+        if rows != self.extra_rows {
+            self.extra_rows = rows;
+            let (width, height) = self.settings.appearance().size();
+            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::Vec2::new(
+                width,
+                height + rows as f32 * row_height,
+            )));
+        }
+        if let Some(list) = &tag_list {
+            if ctx.input(|i| i.key_pressed(egui::Key::ArrowDown)) {
+                self.tag_selection += 1;
+            }
+            if ctx.input(|i| i.key_pressed(egui::Key::ArrowUp)) {
+                self.tag_selection = self.tag_selection.saturating_sub(1);
+            }
+            self.tag_selection = self.tag_selection.min(list.len().saturating_sub(1));
+        }
 
         // Transparent visuals for the command bar
         {
@@ -1111,7 +1482,7 @@ impl eframe::App for LauncherApp {
         egui::CentralPanel::default()
             .frame(egui::Frame::new().fill(bg).inner_margin(8.0))
             .show(ctx, |ui| {
-                let font_color = self.settings.font_color32();
+                let font_color = self.settings.appearance().font_color32();
                 let text_edit_id = egui::Id::new("main_command_input");
 
                 ui.horizontal(|ui| {
@@ -1125,11 +1496,20 @@ impl eframe::App for LauncherApp {
                         .selectable(false),
                     );
 
-                    let input_widget = egui::TextEdit::singleline(&mut self.command_input)
+                    let mut input_widget = egui::TextEdit::singleline(&mut self.command_input)
                         .id(text_edit_id)
                         .font(egui::FontId::monospace(font_size))
                         .desired_width(f32::INFINITY)
                         .text_color(font_color);
+                    if let Some(error) = &self.config_error {
+                        input_widget = input_widget.hint_text(
+                            egui::RichText::new(format!("Config not reloaded: {}", error))
+                                .color(egui::Color32::LIGHT_RED),
+                        );
+                    } else if let Some(notice) = &self.notice {
+                        input_widget = input_widget
+                            .hint_text(egui::RichText::new(notice).color(egui::Color32::GRAY));
+                    }
 
                     let response = ui.add(input_widget);
                     self.text_edit_rect = Some(response.rect);
@@ -1155,23 +1535,29 @@ impl eframe::App for LauncherApp {
 
                     response.request_focus();
 
-                    if tab_pressed {
-                        if let Some(match_name) = self.best_autocomplete(&self.command_input) {
-                            self.command_input = match_name;
-                            if let Some(mut state) =
-                                egui::TextEdit::load_state(ctx, text_edit_id)
-                            {
-                                let ccursor =
-                                    egui::text::CCursor::new(self.command_input.len());
-                                state.cursor.set_char_range(Some(
-                                    egui::text::CCursorRange::one(ccursor),
-                                ));
-                                state.store(ctx, text_edit_id);
-                            }
+                    if tab_pressed
+                        && let Some(match_name) = self.best_autocomplete(&self.command_input)
+                    {
+                        self.command_input = match_name;
+                        if let Some(mut state) =
+                            egui::TextEdit::load_state(ctx, text_edit_id)
+                        {
+                            let ccursor =
+                                egui::text::CCursor::new(self.command_input.len());
+                            state.cursor.set_char_range(Some(
+                                egui::text::CCursorRange::one(ccursor),
+                            ));
+                            state.store(ctx, text_edit_id);
                         }
                     }
 
-                    if enter_pressed {
+                    let listed = tag_list
+                        .as_ref()
+                        .and_then(|list| list.get(self.tag_selection))
+                        .cloned();
+                    if enter_pressed && let Some(mw) = listed {
+                        self.run_listed_word(mw, ctx);
+                    } else if enter_pressed {
                         if let Some(completed) = self.best_autocomplete(&self.command_input) {
                             let first_word =
                                 self.command_input.split_whitespace().next().unwrap_or("");
@@ -1189,17 +1575,47 @@ impl eframe::App for LauncherApp {
                     }
                 });
 
+                if let Some(description) = &description {
+                    ui.label(
+                        egui::RichText::new(description)
+                            .size(font_size * 0.85)
+                            .color(egui::Color32::GRAY),
+                    );
+                }
+                if let Some(list) = &tag_list {
+                    if list.is_empty() {
+                        ui.label(
+                            egui::RichText::new("No magic words with that tag")
+                                .monospace()
+                                .color(egui::Color32::GRAY),
+                        );
+                    }
+                    let mut picked = None;
+                    for (i, mw) in list.iter().enumerate() {
+                        let about = mw.description.as_deref().unwrap_or(&mw.filename_or_url);
+                        let text = egui::RichText::new(format!("{:<12} {}", mw.keyword, about))
+                            .monospace()
+                            .size(font_size * 0.85)
+                            .color(font_color);
+                        if ui.selectable_label(i == self.tag_selection, text).clicked() {
+                            picked = Some(mw.clone());
+                        }
+                    }
+                    if let Some(mw) = picked {
+                        self.run_listed_word(mw, ctx);
+                    }
+                }
+
                 // Drag — click anywhere outside text edit starts drag
                 let pressed = ctx.input(|i| i.pointer.primary_pressed());
-                if pressed {
-                    if let Some(pos) = ctx.input(|i| i.pointer.latest_pos()) {
-                        let in_text_edit = self
-                            .text_edit_rect
-                            .is_some_and(|r: egui::Rect| r.contains(pos));
-                        if !in_text_edit {
-                            self.dragging = true;
-                            ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
-                        }
+                if pressed && let Some(pos) = ctx.input(|i| i.pointer.latest_pos()) {
+                    let in_text_edit = self
+                        .text_edit_rect
+                        .is_some_and(|r: egui::Rect| r.contains(pos));
+                    // Layer surfaces sit where their anchors put them and can't be dragged
+                    if !in_text_edit && !self.desktop.is_wlroots() {
+                        self.dragging = true;
+                        ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
                     }
                 }
             });
@@ -1,21 +1,23 @@
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+use crate::settings::HotkeyConfig;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
 pub enum StartMode {
+    #[default]
     Normal,
     Sudo,
 }
 
-impl Default for StartMode {
-    fn default() -> Self {
-        StartMode::Normal
-    }
-}
-
 #[derive(Serialize, Deserialize, Clone)]
 pub struct MagicWord {
     /// The keyword that triggers this magic word.
     pub keyword: String,
+    /// Other keywords that trigger it too, e.g. "goo" and "google" for "g".
+    #[serde(default)]
+    pub aliases: Vec<String>,
     /// The command, URL, or "@copy@".
     pub filename_or_url: String,
     /// Normal or Sudo.
@@ -25,9 +27,48 @@ pub struct MagicWord {
     pub start_path: Option<String>,
     /// Default parameters. Use $W$ as a placeholder for user-supplied arguments.
     pub parameters: Option<String>,
+    /// Global hotkey that runs this magic word directly.
+    #[serde(default)]
+    pub hotkey: Option<HotkeyConfig>,
+    /// Profiles this word belongs to; empty means every profile.
+    #[serde(default)]
+    pub profiles: Vec<String>,
+    /// Regular expression that triggers this word when the whole input
+    /// matches, e.g. `^JIRA-\d+$`. Capture groups fill in $0$, $1$, … and
+    /// named groups $name$.
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Order among pattern words; higher is tried first.
+    #[serde(default)]
+    pub priority: i32,
+    /// Categories for the Library sidebar and `#tag` search, e.g. "dev".
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// What the word does, for whoever can't remember what `kp` stands for.
+    /// Shown under autocomplete suggestions.
+    #[serde(default)]
+    pub description: Option<String>,
 }
 
 impl MagicWord {
+    /// The keyword followed by its aliases.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.keyword.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+
+    /// Whether the word has a tag starting with `prefix` (case-insensitive).
+    pub fn has_tag_prefix(&self, prefix: &str) -> bool {
+        let prefix = prefix.to_lowercase();
+        self.tags
+            .iter()
+            .any(|t| t.to_lowercase().starts_with(&prefix))
+    }
+
+    /// Whether `keyword` is this word's keyword or one of its aliases.
+    pub fn answers_to(&self, keyword: &str) -> bool {
+        self.names().any(|name| name.eq_ignore_ascii_case(keyword))
+    }
+
     /// Check if the filename_or_url looks like a URL.
     fn is_url(s: &str) -> bool {
         s.starts_with("http://")
@@ -75,15 +116,55 @@ impl MagicWord {
                 .is_some_and(|p| p.contains("$W$"))
     }
 
-    /// Execute this magic word with the given user arguments (text after the keyword).
-    pub fn execute(&self, user_args: &str) {
+    /// A copy with the capture groups of `pattern` matched against `input`
+    /// substituted, or None if the input doesn't match.
+    fn matched(&self, regex: &Regex, input: &str) -> Option<MagicWord> {
+        let captures = regex.captures(input)?;
+        // The group `$name$` refers to: Some(None) if it exists but didn't
+        // take part in the match, None if it isn't a group (e.g. $W$)
+        let group = |name: &str| {
+            if !name.is_empty() && name.bytes().all(|b| b.is_ascii_digit()) {
+                let i: usize = name.parse().ok()?;
+                (i < captures.len()).then(|| captures.get(i))
+            } else {
+                regex.capture_names().flatten().any(|n| n == name).then(|| captures.name(name))
+            }
+        };
+        // One pass, so a captured "$1$" or "$name$" is inserted verbatim
+        // instead of being expanded again
+        let substitute = |text: &str| -> String {
+            let mut out = String::with_capacity(text.len());
+            let mut rest = text;
+            while let Some(start) = rest.find('$') {
+                out.push_str(&rest[..start]);
+                let after = &rest[start + 1..];
+                match after.find('$').and_then(|end| Some((end, group(&after[..end])?))) {
+                    Some((end, value)) => {
+                        out.push_str(value.map_or("", |m| m.as_str()));
+                        rest = &after[end + 1..];
+                    }
+                    None => {
+                        out.push('$');
+                        rest = after;
+                    }
+                }
+            }
+            out.push_str(rest);
+            out
+        };
+        Some(MagicWord {
+            filename_or_url: substitute(&self.filename_or_url),
+            parameters: self.parameters.as_deref().map(substitute),
+            ..self.clone()
+        })
+    }
+
+    /// What running this magic word with the given user arguments (text
+    /// after the keyword) would do.
+    pub fn resolve(&self, user_args: &str) -> Action {
         // @copy@ — copy resolved params to clipboard
         if self.is_copy_command() {
-            let text = self.resolve_params(user_args);
-            if let Ok(mut clipboard) = arboard::Clipboard::new() {
-                let _ = clipboard.set_text(&text);
-            }
-            return;
+            return Action::Copy(self.resolve_params(user_args));
         }
 
         let filename = self.resolve_filename(user_args);
@@ -100,18 +181,12 @@ impl MagicWord {
                 // Append params as query or just pass them
                 format!("{}{}", filename, params)
             };
-            let _ = std::process::Command::new("xdg-open")
-                .arg(&url)
-                .spawn();
-            return;
+            return Action::Open(url);
         }
 
         // Directory — open in file manager
         if std::path::Path::new(&filename).is_dir() {
-            let _ = std::process::Command::new("xdg-open")
-                .arg(&filename)
-                .spawn();
-            return;
+            return Action::Open(filename);
         }
 
         // Regular command
@@ -121,26 +196,158 @@ impl MagicWord {
             format!("{} {}", filename, params)
         };
 
-        let shell_cmd = match self.start_mode {
+        let command = match self.start_mode {
             StartMode::Sudo => format!("sudo {}", full_command),
             StartMode::Normal => full_command,
         };
+        Action::Shell {
+            command,
+            start_path: self.start_path.clone().filter(|path| !path.is_empty()),
+        }
+    }
 
-        let mut cmd = std::process::Command::new("sh");
-        cmd.arg("-c").arg(&shell_cmd);
-        if let Some(path) = &self.start_path {
-            if !path.is_empty() {
-                cmd.current_dir(path);
+    /// Execute this magic word with the given user arguments (text after the keyword).
+    pub fn execute(&self, user_args: &str) {
+        self.resolve(user_args).run();
+    }
+}
+
+/// A magic word with its arguments filled in, ready to run.
+pub enum Action {
+    /// Put the text on the clipboard.
+    Copy(String),
+    /// Open a URL or directory with xdg-open.
+    Open(String),
+    /// Run a command with `sh -c`.
+    Shell { command: String, start_path: Option<String> },
+}
+
+impl Action {
+    pub fn run(&self) {
+        match self {
+            Action::Copy(text) => {
+                if let Ok(mut clipboard) = arboard::Clipboard::new() {
+                    let _ = clipboard.set_text(text);
+                }
+            }
+            Action::Open(target) => {
+                let _ = std::process::Command::new("xdg-open").arg(target).spawn();
             }
+            Action::Shell { command, start_path } => {
+                let mut cmd = std::process::Command::new("sh");
+                cmd.arg("-c").arg(command);
+                if let Some(path) = start_path {
+                    cmd.current_dir(path);
+                }
+                let _ = cmd.spawn();
+            }
+        }
+    }
+
+    /// One line for the edit form's Test panel.
+    pub fn describe(&self) -> String {
+        match self {
+            Action::Copy(text) => format!("Copy to clipboard: {}", text),
+            Action::Open(target) => format!("xdg-open {}", shell_quote(target)),
+            Action::Shell { command, start_path: Some(path) } => {
+                format!("sh -c {}   (in {})", shell_quote(command), path)
+            }
+            Action::Shell { command, start_path: None } => format!("sh -c {}", shell_quote(command)),
+        }
+    }
+}
+
+/// Quote `s` as one POSIX shell word, so a described command can be pasted
+/// into a terminal as is.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Compiled pattern regexes keyed by pattern; None for an invalid one.
+pub type PatternCache = HashMap<String, Option<Regex>>;
+
+/// Make `cache` hold exactly the patterns of `words`, compiling only the new
+/// ones, so typing in the launcher doesn't rebuild every regex per keystroke.
+pub fn compile_patterns<'a>(words: impl Iterator<Item = &'a MagicWord>, cache: &mut PatternCache) {
+    let mut compiled = PatternCache::new();
+    for pattern in words.filter_map(|mw| mw.pattern.as_deref()) {
+        if compiled.contains_key(pattern) {
+            continue;
         }
-        let _ = cmd.spawn();
+        // Anchored, so `JIRA-\d+` doesn't fire on "see JIRA-12 later"
+        let regex = cache
+            .remove(pattern)
+            .unwrap_or_else(|| Regex::new(&format!("^(?:{})$", pattern)).ok());
+        compiled.insert(pattern.to_string(), regex);
     }
+    *cache = compiled;
+}
 
+/// The highest-priority pattern word `input` matches, with its capture groups
+/// filled in. Invalid patterns, and ones missing from `patterns`, are skipped
+/// (validation reports the invalid ones).
+pub fn find_by_pattern<'a>(
+    words: impl Iterator<Item = &'a MagicWord>,
+    patterns: &PatternCache,
+    input: &str,
+) -> Option<MagicWord> {
+    let mut candidates: Vec<&MagicWord> = words.filter(|mw| mw.pattern.is_some()).collect();
+    // Stable, so equal priorities keep list order
+    candidates.sort_by_key(|mw| std::cmp::Reverse(mw.priority));
+    candidates.into_iter().find_map(|mw| {
+        let regex = patterns.get(mw.pattern.as_deref()?)?.as_ref()?;
+        mw.matched(regex, input)
+    })
 }
 
 /// Find a magic word by exact keyword match (case-insensitive).
 pub fn find_by_keyword<'a>(magic_words: &'a [MagicWord], keyword: &str) -> Option<&'a MagicWord> {
-    magic_words
-        .iter()
-        .find(|mw| mw.keyword.eq_ignore_ascii_case(keyword))
+    magic_words.iter().find(|mw| mw.answers_to(keyword))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_quotes_the_shell_command() {
+        let action = Action::Shell {
+            command: "echo 'hi' && ls".into(),
+            start_path: None,
+        };
+        assert_eq!(action.describe(), r#"sh -c 'echo '\''hi'\'' && ls'"#);
+        let action = Action::Open("https://example.com/?q=a b".into());
+        assert_eq!(action.describe(), "xdg-open 'https://example.com/?q=a b'");
+    }
+
+    fn pattern_word(pattern: &str, filename_or_url: &str) -> MagicWord {
+        serde_json::from_value(serde_json::json!({
+            "keyword": "p",
+            "filename_or_url": filename_or_url,
+            "pattern": pattern,
+        }))
+        .unwrap()
+    }
+
+    fn run_pattern(mw: &MagicWord, input: &str) -> Option<String> {
+        let mut patterns = PatternCache::new();
+        compile_patterns(std::iter::once(mw), &mut patterns);
+        find_by_pattern(std::iter::once(mw), &patterns, input).map(|m| m.filename_or_url)
+    }
+
+    #[test]
+    fn captures_are_substituted_in_one_pass() {
+        let mw = pattern_word(r"(?P<key>\S+) (\S*)", "echo $key$ $2$ $1$ $0$ $W$ $$ $nope$");
+        assert_eq!(
+            run_pattern(&mw, "a$2$ b").as_deref(),
+            Some("echo a$2$ b a$2$ a$2$ b $W$ $$ $nope$")
+        );
+        // A group that took no part in the match is left empty
+        let mw = pattern_word(r"(a)|(b)", "[$1$][$2$]");
+        assert_eq!(run_pattern(&mw, "b").as_deref(), Some("[][b]"));
+        // Anchored to the whole input
+        let mw = pattern_word(r"JIRA-(\d+)", "https://jira/$1$");
+        assert_eq!(run_pattern(&mw, "see JIRA-12"), None);
+        assert_eq!(run_pattern(&mw, "JIRA-12").as_deref(), Some("https://jira/12"));
+    }
 }
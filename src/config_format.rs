@@ -0,0 +1,256 @@
+use std::path::Path;
+
+use serde::Serialize;
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table};
+
+/// On-disk format of a config or word file, chosen by extension.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// TOML for `*.toml` and its backups (`config.toml.1`, `config.toml.broken`),
+    /// JSON for everything else.
+    pub fn of(path: &Path) -> Self {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_lowercase();
+        if name.ends_with(".toml") || name.contains(".toml.") {
+            ConfigFormat::Toml
+        } else {
+            ConfigFormat::Json
+        }
+    }
+
+    /// Parse `data` into a JSON value, so migrations only deal with one shape.
+    pub fn parse(self, data: &str) -> Result<serde_json::Value, String> {
+        match self {
+            ConfigFormat::Json => serde_json::from_str(data).map_err(|e| e.to_string()),
+            ConfigFormat::Toml => toml_edit::de::from_str(data).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Serialize `value`. For TOML, `previous` is the file being replaced:
+    /// its comments and layout are kept for every key and magic word that
+    /// still exists.
+    pub fn render(self, value: &impl Serialize, previous: Option<&str>) -> Result<String, String> {
+        match self {
+            ConfigFormat::Json => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+            ConfigFormat::Toml => {
+                let mut doc = toml_edit::ser::to_document(value).map_err(|e| e.to_string())?;
+                expand_arrays_of_tables(doc.as_table_mut());
+                let Some(mut old) = previous.and_then(|p| p.parse::<DocumentMut>().ok()) else {
+                    let mut doc = doc.to_string();
+                    doc.insert_str(0, "# SlickRun configuration. Comments are kept when SlickRun saves.\n\n");
+                    return Ok(doc);
+                };
+                merge_table(old.as_table_mut(), doc.as_table());
+                Ok(old.to_string())
+            }
+        }
+    }
+}
+
+/// The serializer writes lists of structs as inline arrays on one line;
+/// turn top-level ones into `[[magic_words]]` sections that can be read and
+/// commented one entry at a time.
+fn expand_arrays_of_tables(table: &mut Table) {
+    for (_, item) in table.iter_mut() {
+        let Some(array) = item
+            .as_array()
+            .filter(|a| !a.is_empty() && a.iter().all(|v| v.is_inline_table()))
+        else {
+            continue;
+        };
+        let mut tables = ArrayOfTables::new();
+        for value in array.iter().filter_map(|v| v.as_inline_table()) {
+            tables.push(value.clone().into_table());
+        }
+        *item = Item::ArrayOfTables(tables);
+    }
+}
+
+/// Bring `old` up to date with `new`, keeping the comments and formatting of
+/// whatever is unchanged.
+fn merge_table(old: &mut Table, new: &Table) {
+    let stale: Vec<String> = old
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| !new.contains_key(key))
+        .collect();
+    for key in stale {
+        old.remove(&key);
+    }
+    for (key, new_item) in new.iter() {
+        match old.get_mut(key) {
+            Some(old_item) => merge_item(old_item, new_item),
+            None => {
+                old.insert(key, new_item.clone());
+            }
+        }
+    }
+}
+
+fn merge_item(old: &mut Item, new: &Item) {
+    match (old, new) {
+        (Item::Table(old), Item::Table(new)) => merge_table(old, new),
+        (Item::ArrayOfTables(old), Item::ArrayOfTables(new)) => {
+            // Entries are matched by keyword, so a comment stays with its magic
+            // word when words are added, removed or reordered
+            let keyword = |t: &Table| t.get("keyword").and_then(|k| k.as_str()).map(str::to_string);
+            let mut merged = ArrayOfTables::new();
+            for new_table in new.iter() {
+                let previous = keyword(new_table)
+                    .and_then(|k| old.iter().find(|t| keyword(t).as_deref() == Some(k.as_str())));
+                match previous {
+                    Some(previous) => {
+                        let mut table = previous.clone();
+                        merge_table(&mut table, new_table);
+                        merged.push(table);
+                    }
+                    None => merged.push(new_table.clone()),
+                }
+            }
+            // Sections are written in position order; pin them all to the
+            // first old one so the list comes out in its new order
+            if let Some(first) = old.iter().filter_map(|t| t.position()).min() {
+                for table in merged.iter_mut() {
+                    table.set_position(first);
+                }
+            }
+            *old = merged;
+        }
+        (Item::Value(old), Item::Value(new)) => {
+            if old.to_string().trim() != new.to_string().trim() {
+                let decor = old.decor().clone();
+                *old = new.clone();
+                *old.decor_mut() = decor;
+            }
+        }
+        (old, new) => *old = new.clone(),
+    }
+}
+
+/// `slickrun --convert-config [INPUT] OUTPUT`: rewrite a config (by default
+/// the live one) in the format OUTPUT's extension names. Returns the process
+/// exit code.
+pub fn convert_command(args: &[String]) -> i32 {
+    use crate::settings::Settings;
+
+    let (input, output) = match args {
+        [output] => (Settings::config_path(), std::path::PathBuf::from(output)),
+        [input, output] => (input.into(), output.into()),
+        _ => {
+            eprintln!("usage: slickrun --convert-config [INPUT] OUTPUT   (format from OUTPUT's extension: .json or .toml)");
+            return 2;
+        }
+    };
+    if output.exists() {
+        eprintln!("{} already exists — not overwriting it", output.display());
+        return 1;
+    }
+    let settings = match Settings::load_from(&input) {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("{}: {}", input.display(), e);
+            return 1;
+        }
+    };
+    let written = ConfigFormat::of(&output)
+        .render(&settings, None)
+        .and_then(|data| std::fs::write(&output, data).map_err(|e| e.to_string()));
+    if let Err(e) = written {
+        eprintln!("{}: {}", output.display(), e);
+        return 1;
+    }
+    println!("Wrote {} ({} magic words)", output.display(), settings.magic_words.len());
+    if output == Settings::toml_config_path() {
+        println!(
+            "SlickRun now reads {}; {} is no longer used",
+            output.display(),
+            Settings::json_config_path().display()
+        );
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Settings;
+
+    fn commented_config() -> String {
+        format!(
+            r#"# My launcher
+schema_version = {}
+font_color = [0, 200, 120]
+window_width = 400.0 # wide enough for URLs
+window_height = 48.0
+hide_on_inactive = true
+opacity_percent = 100
+
+# Search the web
+[[magic_words]]
+keyword = "g"
+filename_or_url = "https://www.google.com/search?q=$W$"
+
+# Editor
+[[magic_words]]
+keyword = "ed"
+filename_or_url = "code"
+
+[[magic_words]]
+keyword = "term"
+filename_or_url = "kitty"
+"#,
+            crate::migrations::CURRENT_VERSION
+        )
+    }
+
+    fn load(format: ConfigFormat, data: &str) -> Settings {
+        let mut value = format.parse(data).unwrap();
+        crate::migrations::migrate(&mut value).unwrap();
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn toml_comments_follow_their_words_through_json() {
+        let original = commented_config();
+        let settings = load(ConfigFormat::Toml, &original);
+        let json = ConfigFormat::Json.render(&settings, None).unwrap();
+        let mut settings = load(ConfigFormat::Json, &json);
+
+        settings.magic_words.reverse();
+        settings.magic_words[0].keyword = "t".into();
+        settings.window_width = 500.0;
+        let rendered = ConfigFormat::Toml
+            .render(&settings, Some(&original))
+            .unwrap();
+
+        assert!(rendered.starts_with("# My launcher\n"));
+        assert!(rendered.contains("window_width = 500.0 # wide enough for URLs\n"));
+        let term = rendered.find("keyword = \"t\"").unwrap();
+        let editor = rendered
+            .find("# Editor\n[[magic_words]]\nkeyword = \"ed\"")
+            .unwrap();
+        let search = rendered
+            .find("# Search the web\n[[magic_words]]\nkeyword = \"g\"")
+            .unwrap();
+        assert!(term < editor && editor < search);
+
+        let reloaded = load(ConfigFormat::Toml, &rendered);
+        let words: Vec<(&str, &str)> = reloaded
+            .magic_words
+            .iter()
+            .map(|mw| (mw.keyword.as_str(), mw.filename_or_url.as_str()))
+            .collect();
+        assert_eq!(
+            words,
+            [
+                ("t", "kitty"),
+                ("ed", "code"),
+                ("g", "https://www.google.com/search?q=$W$")
+            ]
+        );
+    }
+}
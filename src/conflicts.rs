@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+
+use global_hotkey::GlobalHotKeyManager;
+
+use crate::app::gnome_custom_keybinding_paths;
+use crate::keys;
+use crate::kwin;
+use crate::settings::HotkeyConfig;
+
+/// A shortcut some other part of the desktop already owns.
+pub struct DesktopBinding {
+    /// Human-readable owner, e.g. "GNOME window manager: close".
+    pub owner: String,
+    /// As the desktop spells it: "<Super>q" on GNOME, "Meta+Q" on Plasma.
+    pub accelerator: String,
+    /// Modifier mask and lowercase keysym, to compare with our hotkeys.
+    keys: (u8, String),
+}
+
+/// gsettings schemas whose keys are lists of accelerators.
+const GNOME_SCHEMAS: &[(&str, &str)] = &[
+    ("org.gnome.desktop.wm.keybindings", "GNOME window manager"),
+    ("org.gnome.settings-daemon.plugins.media-keys", "GNOME media keys"),
+    ("org.gnome.shell.keybindings", "GNOME Shell"),
+    ("org.gnome.mutter.keybindings", "Mutter"),
+    ("org.gnome.mutter.wayland.keybindings", "Mutter"),
+];
+
+const SUPER: u8 = 1;
+const CTRL: u8 = 2;
+const SHIFT: u8 = 4;
+const ALT: u8 = 8;
+const OTHER: u8 = 16;
+
+/// Parse "<Super><Shift>q" into a modifier mask and lowercase keysym.
+fn parse_accelerator(accel: &str) -> Option<(u8, String)> {
+    let mut mods = 0;
+    let mut rest = accel.trim();
+    while let Some(tail) = rest.strip_prefix('<') {
+        let (name, after) = tail.split_once('>')?;
+        mods |= match name.to_lowercase().as_str() {
+            "super" | "mod4" => SUPER,
+            "control" | "ctrl" | "primary" => CTRL,
+            "shift" => SHIFT,
+            "alt" | "mod1" => ALT,
+            _ => OTHER,
+        };
+        rest = after;
+    }
+    if rest.is_empty() {
+        return None;
+    }
+    Some((mods, rest.to_lowercase()))
+}
+
+fn hotkey_accelerator(hotkey: &HotkeyConfig) -> (u8, String) {
+    let mut mods = 0;
+    if hotkey.super_key {
+        mods |= SUPER;
+    }
+    if hotkey.ctrl {
+        mods |= CTRL;
+    }
+    if hotkey.shift {
+        mods |= SHIFT;
+    }
+    if hotkey.alt {
+        mods |= ALT;
+    }
+    (mods, keys::keysym(&hotkey.key).to_lowercase())
+}
+
+fn gsettings(args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new("gsettings").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// The quoted strings in a GVariant value, e.g. "['<Alt>F4', '<Super>q']".
+fn quoted_strings(value: &str) -> impl Iterator<Item = &str> {
+    value.split('\'').skip(1).step_by(2)
+}
+
+/// Every keybinding GNOME already has: built-in wm/media-keys/shell bindings
+/// plus custom shortcuts that aren't ours.
+pub fn gnome_bindings() -> Vec<DesktopBinding> {
+    let mut bindings = Vec::new();
+    for (schema, owner) in GNOME_SCHEMAS {
+        // Schemas missing on this GNOME version just produce no output
+        let Some(out) = gsettings(&["list-recursively", schema]) else {
+            continue;
+        };
+        for line in out.lines() {
+            let mut fields = line.splitn(3, ' ');
+            let (Some(_), Some(key), Some(value)) = (fields.next(), fields.next(), fields.next()) else {
+                continue;
+            };
+            for accel in quoted_strings(value) {
+                // custom-keybindings lists paths, not accelerators
+                if accel.starts_with('/') {
+                    continue;
+                }
+                let Some(keys) = parse_accelerator(accel) else {
+                    continue;
+                };
+                bindings.push(DesktopBinding {
+                    owner: format!("{}: {}", owner, key),
+                    accelerator: accel.to_string(),
+                    keys,
+                });
+            }
+        }
+    }
+
+    for path in gnome_custom_keybinding_paths().unwrap_or_default() {
+        if path.contains("/slickrun") {
+            continue;
+        }
+        let schema = format!(
+            "org.gnome.settings-daemon.plugins.media-keys.custom-keybinding:{}",
+            path
+        );
+        let Some(binding) = gsettings(&["get", &schema, "binding"]) else {
+            continue;
+        };
+        let name = gsettings(&["get", &schema, "name"]).unwrap_or_default();
+        let name = name.trim().trim_matches('\'');
+        for accel in quoted_strings(&binding) {
+            let Some(keys) = parse_accelerator(accel) else {
+                continue;
+            };
+            bindings.push(DesktopBinding {
+                owner: format!("custom shortcut \"{}\"", name),
+                accelerator: accel.to_string(),
+                keys,
+            });
+        }
+    }
+    bindings
+}
+
+/// Parse a Qt key sequence like "Meta+Shift+Q" into the same form as
+/// `parse_accelerator`.
+fn parse_qt_shortcut(shortcut: &str) -> Option<(u8, String)> {
+    // The key itself may be "+", as in "Ctrl++"
+    let (mods, key) = match shortcut.strip_suffix("++") {
+        Some(mods) => (mods, "+"),
+        None => shortcut.rsplit_once('+').unwrap_or(("", shortcut)),
+    };
+    if key.is_empty() {
+        return None;
+    }
+    let mut mask = 0;
+    for name in mods.split('+').filter(|m| !m.is_empty()) {
+        mask |= match name.to_lowercase().as_str() {
+            "meta" => SUPER,
+            "ctrl" => CTRL,
+            "shift" => SHIFT,
+            "alt" => ALT,
+            _ => OTHER,
+        };
+    }
+    let keysym = keys::KEYS
+        .iter()
+        .find(|k| k.qt.eq_ignore_ascii_case(key))
+        .map_or(key, |k| k.keysym);
+    Some((mask, keysym.to_lowercase()))
+}
+
+/// Every Plasma global shortcut in kglobalshortcutsrc except our own.
+pub fn kde_bindings() -> Vec<DesktopBinding> {
+    let path = dirs::config_dir().unwrap_or_default().join("kglobalshortcutsrc");
+    std::fs::read_to_string(path)
+        .map(|data| parse_kglobalshortcutsrc(&data))
+        .unwrap_or_default()
+}
+
+fn parse_kglobalshortcutsrc(data: &str) -> Vec<DesktopBinding> {
+    let mut bindings = Vec::new();
+    let mut component = "";
+    for line in data.lines().map(str::trim) {
+        if let Some(group) = line.strip_prefix('[') {
+            // "[kwin]", or "[services][org.kde.konsole.desktop]" for launchers
+            component = group.trim_end_matches(']').rsplit("][").next().unwrap_or_default();
+            continue;
+        }
+        let Some((action, value)) = line.split_once('=') else {
+            continue;
+        };
+        if action == "_k_friendly_name" || component == kwin::SHORTCUT_DESKTOP_FILE {
+            continue;
+        }
+        let owner = if action == "_launch" {
+            format!("Plasma launcher {}", component)
+        } else {
+            format!("Plasma {}: {}", component, action)
+        };
+        // "active,default,description"; KConfig escapes the tab between
+        // several active shortcuts as a literal \t
+        let active = value.split(',').next().unwrap_or_default();
+        for shortcut in active.split(r"\t").filter(|s| !s.is_empty() && *s != "none") {
+            let Some(keys) = parse_qt_shortcut(shortcut) else {
+                continue;
+            };
+            bindings.push(DesktopBinding {
+                owner: owner.clone(),
+                accelerator: shortcut.to_string(),
+                keys,
+            });
+        }
+    }
+    bindings
+}
+
+/// The existing binding `hotkey` would collide with, if any.
+pub fn find_binding<'a>(hotkey: &HotkeyConfig, bindings: &'a [DesktopBinding]) -> Option<&'a DesktopBinding> {
+    let wanted = hotkey_accelerator(hotkey);
+    bindings.iter().find(|b| b.keys == wanted)
+}
+
+/// Finds out which hotkeys other X clients hold passive grabs on. Every probe
+/// goes through one manager (a thread and an X connection), created on first
+/// use, and each answer is cached.
+#[derive(Default)]
+pub struct GrabProbe {
+    manager: Option<GlobalHotKeyManager>,
+    taken: HashMap<u32, bool>,
+}
+
+impl GrabProbe {
+    /// Whether another X client already grabs `hotkey`.
+    pub fn is_taken(&mut self, hotkey: &HotkeyConfig) -> bool {
+        if std::env::var_os("DISPLAY").is_none() {
+            return false;
+        }
+        let Some(hk) = hotkey.to_hotkey() else {
+            return false;
+        };
+        if let Some(&taken) = self.taken.get(&hk.id()) {
+            return taken;
+        }
+        if self.manager.is_none() {
+            self.manager = GlobalHotKeyManager::new().ok();
+        }
+        let Some(manager) = &self.manager else {
+            return false;
+        };
+        let taken = match manager.register(hk) {
+            Ok(()) => {
+                // Release it again so the real registration can have it
+                let _ = manager.unregister(hk);
+                false
+            }
+            Err(global_hotkey::Error::AlreadyRegistered(_)) => true,
+            Err(_) => false,
+        };
+        self.taken.insert(hk.id(), taken);
+        taken
+    }
+
+    /// Forget cached answers; other applications may have changed their grabs.
+    pub fn clear(&mut self) {
+        self.taken.clear();
+    }
+}
+
+/// Up to three nearby hotkeys that are free, i.e. `is_taken` returns false
+/// for them: the same key with other modifiers first, then the same
+/// modifiers on other keys.
+pub fn suggest_alternatives(
+    hotkey: &HotkeyConfig,
+    mut is_taken: impl FnMut(&HotkeyConfig) -> bool,
+) -> Vec<HotkeyConfig> {
+    // (super, ctrl, shift, alt)
+    const MODIFIERS: &[(bool, bool, bool, bool)] = &[
+        (true, false, true, false),
+        (false, true, false, true),
+        (true, false, false, true),
+        (true, true, false, false),
+        (false, true, true, true),
+        (true, true, true, false),
+    ];
+    const KEYS: &[&str] = &["Space", "R", "J", "K", "Semicolon", "Period", "Slash", "F12"];
+
+    let with_mods = MODIFIERS.iter().map(|&(super_key, ctrl, shift, alt)| HotkeyConfig {
+        super_key,
+        ctrl,
+        shift,
+        alt,
+        key: hotkey.key.clone(),
+    });
+    let with_keys = KEYS.iter().map(|k| HotkeyConfig {
+        key: k.to_string(),
+        ..hotkey.clone()
+    });
+    with_mods
+        .chain(with_keys)
+        .filter(|candidate| candidate != hotkey && !is_taken(candidate))
+        .take(3)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kglobalshortcutsrc_active_shortcuts_only() {
+        let data = "[kwin]\n_k_friendly_name=KWin\nWindow Close=Alt+F4\\tMeta+Q,Alt+F4,Close Window\nExpose=none,Ctrl+F9,Toggle Present Windows\n\n[services][org.kde.konsole.desktop]\n_launch=Ctrl+Alt+T\n\n[services][slickrun-toggle.desktop]\n_launch=Meta+Space\n";
+        let bindings = parse_kglobalshortcutsrc(data);
+        let found: Vec<(&str, &str)> = bindings
+            .iter()
+            .map(|b| (b.owner.as_str(), b.accelerator.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("Plasma kwin: Window Close", "Alt+F4"),
+                ("Plasma kwin: Window Close", "Meta+Q"),
+                ("Plasma launcher org.kde.konsole.desktop", "Ctrl+Alt+T"),
+            ]
+        );
+        assert_eq!(bindings[1].keys, (SUPER, "q".to_string()));
+        assert_eq!(bindings[2].keys, (CTRL | ALT, "t".to_string()));
+    }
+
+    #[test]
+    fn qt_shortcut_with_plus_key() {
+        assert_eq!(
+            parse_qt_shortcut("Ctrl++").map(|(mask, _)| mask),
+            Some(CTRL)
+        );
+        assert_eq!(
+            parse_qt_shortcut("Meta+Shift+Q"),
+            Some((SUPER | SHIFT, "q".to_string()))
+        );
+        assert_eq!(
+            parse_qt_shortcut("Hyper+Q").map(|(mask, _)| mask),
+            Some(OTHER)
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use crate::wlroots::Compositor;
+
+/// Desktop environment, detected once at startup from the session environment.
+/// Selects how the window is activated/moved and how the shortcut is registered.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Desktop {
+    Gnome,
+    Kde,
+    Wlroots(Compositor),
+    Other,
+}
+
+impl Desktop {
+    pub fn detect() -> Self {
+        // Compositor IPC sockets are the most reliable signal for wlroots sessions
+        if std::env::var_os("HYPRLAND_INSTANCE_SIGNATURE").is_some() {
+            return Desktop::Wlroots(Compositor::Hyprland);
+        }
+        if std::env::var_os("SWAYSOCK").is_some() {
+            return Desktop::Wlroots(Compositor::Sway);
+        }
+
+        let current = std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default();
+        // XDG_CURRENT_DESKTOP is a colon-separated list, e.g. "ubuntu:GNOME"
+        for name in current.split(':') {
+            if name.eq_ignore_ascii_case("KDE") {
+                return Desktop::Kde;
+            }
+            if name.eq_ignore_ascii_case("GNOME") {
+                return Desktop::Gnome;
+            }
+            if name.eq_ignore_ascii_case("sway") {
+                return Desktop::Wlroots(Compositor::Sway);
+            }
+            if name.eq_ignore_ascii_case("Hyprland") {
+                return Desktop::Wlroots(Compositor::Hyprland);
+            }
+            if name.eq_ignore_ascii_case("river") {
+                return Desktop::Wlroots(Compositor::River);
+            }
+        }
+        Desktop::Other
+    }
+
+    /// wlroots compositors run us as a native Wayland client; everything else
+    /// goes through XWayland so global-hotkey's XGrabKey works.
+    pub fn is_wlroots(self) -> bool {
+        matches!(self, Desktop::Wlroots(_))
+    }
+}
@@ -0,0 +1,269 @@
+use std::path::{Path, PathBuf};
+
+use crate::commands::{self, MagicWord};
+use crate::settings::Settings;
+use crate::srl;
+
+/// What to do with an imported word whose keyword is already taken.
+#[derive(Clone, PartialEq)]
+pub enum Resolution {
+    KeepMine,
+    TakeTheirs,
+    Rename(String),
+}
+
+pub struct IncomingWord {
+    pub word: MagicWord,
+    pub resolution: Resolution,
+}
+
+impl IncomingWord {
+    /// Index of the existing word answering to the same keyword. Looked up on
+    /// every use rather than stored, since the library can change while the
+    /// preview is open.
+    pub fn collides_with(&self, existing: &[MagicWord]) -> Option<usize> {
+        existing.iter().position(|mw| mw.answers_to(&self.word.keyword))
+    }
+}
+
+/// An import that has been read and matched against the library but not
+/// applied yet, so the user can preview it and resolve collisions.
+pub struct PendingImport {
+    pub source: PathBuf,
+    pub incoming: Vec<IncomingWord>,
+    /// Replace the whole library instead of merging into it.
+    pub replace: bool,
+    /// Conversion notes and unconvertible entries (SlickRun libraries only).
+    pub notes: Vec<String>,
+}
+
+impl PendingImport {
+    /// Read `path` — a Windows SlickRun .srl/.qrs library, an exported word
+    /// list or a whole config.json. Later entries reusing a keyword from the
+    /// same file are dropped with a note.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let (words, mut notes) = if extension == "srl" || extension == "qrs" {
+            let result = srl::import_file(path)?;
+            let mut notes = result.notes;
+            notes.extend(result.skipped.into_iter().map(|s| format!("not imported — {}", s)));
+            (result.words, notes)
+        } else {
+            (Settings::import_magic_words(path)?, Vec::new())
+        };
+
+        let mut incoming: Vec<IncomingWord> = Vec::new();
+        for word in words {
+            if incoming.iter().any(|w| w.word.answers_to(&word.keyword)) {
+                notes.push(format!(
+                    "not imported — \"{}\" appears more than once in the file, kept the first",
+                    word.keyword
+                ));
+                continue;
+            }
+            incoming.push(IncomingWord { word, resolution: Resolution::KeepMine });
+        }
+        Ok(Self {
+            source: path.to_path_buf(),
+            incoming,
+            replace: false,
+            notes,
+        })
+    }
+
+    pub fn collisions(&self, existing: &[MagicWord]) -> usize {
+        self.incoming.iter().filter(|w| w.collides_with(existing).is_some()).count()
+    }
+
+    /// Why the new keyword chosen for entry `index` can't be used.
+    pub fn rename_problem(&self, index: usize, existing: &[MagicWord]) -> Option<String> {
+        let Resolution::Rename(name) = &self.incoming[index].resolution else {
+            return None;
+        };
+        if name.trim().is_empty() || name.contains(char::is_whitespace) {
+            return Some("enter a keyword without spaces".into());
+        }
+        let taken_by_library = commands::find_by_keyword(existing, name).is_some();
+        let taken_by_import = (0..self.incoming.len())
+            .filter(|&i| i != index)
+            .any(|i| self.final_keyword(i, existing).is_some_and(|k| k.eq_ignore_ascii_case(name)));
+        (taken_by_library || taken_by_import).then(|| format!("\"{}\" is already taken", name))
+    }
+
+    /// The keyword entry `index` ends up under, or None if it is dropped.
+    fn final_keyword(&self, index: usize, existing: &[MagicWord]) -> Option<&str> {
+        let w = &self.incoming[index];
+        match (&w.resolution, w.collides_with(existing)) {
+            (_, None) | (Resolution::TakeTheirs, _) => Some(&w.word.keyword),
+            (Resolution::Rename(name), _) => Some(name),
+            (Resolution::KeepMine, _) => None,
+        }
+    }
+
+    pub fn is_ready(&self, existing: &[MagicWord]) -> bool {
+        self.replace || (0..self.incoming.len()).all(|i| self.rename_problem(i, existing).is_none())
+    }
+
+    /// Apply to `words` and describe what happened.
+    pub fn apply(self, words: &mut Vec<MagicWord>) -> String {
+        if self.replace {
+            *words = self.incoming.into_iter().map(|w| w.word).collect();
+            return format!("Replaced library with {} magic words", words.len());
+        }
+
+        let (mut added, mut replaced, mut kept) = (0, 0, 0);
+        for IncomingWord { mut word, resolution } in self.incoming {
+            let collides_with = words.iter().position(|mw| mw.answers_to(&word.keyword));
+            match (collides_with, resolution) {
+                (None, _) => {
+                    words.push(word);
+                    added += 1;
+                }
+                (Some(i), Resolution::TakeTheirs) => {
+                    words[i] = word;
+                    replaced += 1;
+                }
+                (Some(_), Resolution::Rename(name)) => {
+                    word.keyword = name;
+                    words.push(word);
+                    added += 1;
+                }
+                (Some(_), Resolution::KeepMine) => kept += 1,
+            }
+        }
+        format!(
+            "Imported: {} added, {} replaced, {} kept as they were",
+            added, replaced, kept
+        )
+    }
+}
+
+/// Outcome of `pick_file`, delivered to the UI thread.
+pub type PickedFile = Result<Option<PathBuf>, String>;
+
+/// Run `pick_file` on a thread so the dialog doesn't freeze the settings
+/// window; poll the receiver each frame. Repaints `ctx` when the user is done.
+pub fn pick_file_async(ctx: &eframe::egui::Context) -> std::sync::mpsc::Receiver<PickedFile> {
+    let (tx, rx) = std::sync::mpsc::channel();
+    let ctx = ctx.clone();
+    std::thread::spawn(move || {
+        let _ = tx.send(pick_file());
+        ctx.request_repaint();
+    });
+    rx
+}
+
+/// Ask for a file with zenity or kdialog. Ok(None) means the user cancelled;
+/// Err means neither tool is installed and the caller should ask for a path.
+fn pick_file() -> PickedFile {
+    let home = dirs::home_dir().unwrap_or_default().to_string_lossy().to_string();
+    let home_dir = format!("{}/", home);
+    let pickers: [(&str, Vec<&str>); 2] = [
+        (
+            "zenity",
+            vec![
+                "--file-selection",
+                "--title=Import magic words",
+                "--file-filter=Magic words | *.json *.srl *.qrs",
+                "--file-filter=All files | *",
+            ],
+        ),
+        (
+            "kdialog",
+            vec![
+                "--title",
+                "Import magic words",
+                "--getopenfilename",
+                &home_dir,
+                "*.json *.srl *.qrs",
+            ],
+        ),
+    ];
+    for (tool, args) in pickers {
+        match std::process::Command::new(tool).args(&args).output() {
+            Ok(out) if out.status.success() => {
+                let path = String::from_utf8_lossy(&out.stdout).trim().to_string();
+                return Ok((!path.is_empty()).then(|| PathBuf::from(path)));
+            }
+            // Non-zero exit is the user pressing Cancel
+            Ok(_) => return Ok(None),
+            Err(_) => continue,
+        }
+    }
+    Err("neither zenity nor kdialog is installed".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn word(keyword: &str, target: &str) -> MagicWord {
+        serde_json::from_value(json!({ "keyword": keyword, "filename_or_url": target })).unwrap()
+    }
+
+    fn pending(incoming: Vec<MagicWord>) -> PendingImport {
+        PendingImport {
+            source: PathBuf::from("words.json"),
+            incoming: incoming
+                .into_iter()
+                .map(|word| IncomingWord {
+                    word,
+                    resolution: Resolution::TakeTheirs,
+                })
+                .collect(),
+            replace: false,
+            notes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn apply_finds_collisions_in_the_library_as_it_is_now() {
+        let mut library = vec![word("a", "mine-a"), word("g", "mine-g")];
+        let import = pending(vec![word("g", "theirs-g")]);
+        assert_eq!(import.collisions(&library), 1);
+
+        // "a" deleted while the preview was open: "g" moved from index 1 to 0
+        library.remove(0);
+        import.apply(&mut library);
+        assert_eq!(library.len(), 1);
+        assert_eq!(library[0].filename_or_url, "theirs-g");
+    }
+
+    #[test]
+    fn apply_adds_words_whose_collision_went_away() {
+        let mut library = vec![word("g", "mine-g")];
+        let import = pending(vec![word("g", "theirs-g")]);
+        library.clear();
+        import.apply(&mut library);
+        assert_eq!(library.len(), 1);
+        assert_eq!(library[0].filename_or_url, "theirs-g");
+    }
+
+    #[test]
+    fn load_keeps_the_first_of_duplicate_keywords() {
+        let path =
+            std::env::temp_dir().join(format!("slickrun-import-{}.json", std::process::id()));
+        let words = json!([
+            { "keyword": "g", "filename_or_url": "first" },
+            { "keyword": "G", "filename_or_url": "second" },
+            { "keyword": "y", "filename_or_url": "other" },
+        ]);
+        std::fs::write(&path, words.to_string()).unwrap();
+        let import = PendingImport::load(&path);
+        let _ = std::fs::remove_file(&path);
+
+        let import = import.unwrap();
+        let targets: Vec<&str> = import
+            .incoming
+            .iter()
+            .map(|w| w.word.filename_or_url.as_str())
+            .collect();
+        assert_eq!(targets, ["first", "other"]);
+        assert_eq!(import.notes.len(), 1);
+        assert!(import.notes[0].contains("\"G\""));
+    }
+}
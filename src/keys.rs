@@ -0,0 +1,162 @@
+use eframe::egui::Key;
+use global_hotkey::hotkey::Code;
+
+/// One key a hotkey can be bound to, with its name in every format we emit.
+pub struct KeyInfo {
+    /// Name stored in config.json and shown in the UI.
+    pub name: &'static str,
+    pub code: Code,
+    /// X keysym name — GNOME accelerators and wlroots bindings use these.
+    pub keysym: &'static str,
+    /// Qt key name for kglobalaccel.
+    pub qt: &'static str,
+    /// The egui key the recorder sees for it; egui has no numpad or media keys.
+    pub egui: Option<Key>,
+}
+
+const fn key(
+    name: &'static str,
+    code: Code,
+    keysym: &'static str,
+    qt: &'static str,
+    egui: Option<Key>,
+) -> KeyInfo {
+    KeyInfo { name, code, keysym, qt, egui }
+}
+
+/// Every bindable key, in the order the key picker lists them.
+/// F13–F24 and NumpadEnter have no X11 grab in global-hotkey, so they only
+/// work where the desktop binds the shortcut (GNOME, KDE, wlroots).
+pub const KEYS: &[KeyInfo] = &[
+    key("A", Code::KeyA, "a", "A", Some(Key::A)),
+    key("B", Code::KeyB, "b", "B", Some(Key::B)),
+    key("C", Code::KeyC, "c", "C", Some(Key::C)),
+    key("D", Code::KeyD, "d", "D", Some(Key::D)),
+    key("E", Code::KeyE, "e", "E", Some(Key::E)),
+    key("F", Code::KeyF, "f", "F", Some(Key::F)),
+    key("G", Code::KeyG, "g", "G", Some(Key::G)),
+    key("H", Code::KeyH, "h", "H", Some(Key::H)),
+    key("I", Code::KeyI, "i", "I", Some(Key::I)),
+    key("J", Code::KeyJ, "j", "J", Some(Key::J)),
+    key("K", Code::KeyK, "k", "K", Some(Key::K)),
+    key("L", Code::KeyL, "l", "L", Some(Key::L)),
+    key("M", Code::KeyM, "m", "M", Some(Key::M)),
+    key("N", Code::KeyN, "n", "N", Some(Key::N)),
+    key("O", Code::KeyO, "o", "O", Some(Key::O)),
+    key("P", Code::KeyP, "p", "P", Some(Key::P)),
+    key("Q", Code::KeyQ, "q", "Q", Some(Key::Q)),
+    key("R", Code::KeyR, "r", "R", Some(Key::R)),
+    key("S", Code::KeyS, "s", "S", Some(Key::S)),
+    key("T", Code::KeyT, "t", "T", Some(Key::T)),
+    key("U", Code::KeyU, "u", "U", Some(Key::U)),
+    key("V", Code::KeyV, "v", "V", Some(Key::V)),
+    key("W", Code::KeyW, "w", "W", Some(Key::W)),
+    key("X", Code::KeyX, "x", "X", Some(Key::X)),
+    key("Y", Code::KeyY, "y", "Y", Some(Key::Y)),
+    key("Z", Code::KeyZ, "z", "Z", Some(Key::Z)),
+    key("0", Code::Digit0, "0", "0", Some(Key::Num0)),
+    key("1", Code::Digit1, "1", "1", Some(Key::Num1)),
+    key("2", Code::Digit2, "2", "2", Some(Key::Num2)),
+    key("3", Code::Digit3, "3", "3", Some(Key::Num3)),
+    key("4", Code::Digit4, "4", "4", Some(Key::Num4)),
+    key("5", Code::Digit5, "5", "5", Some(Key::Num5)),
+    key("6", Code::Digit6, "6", "6", Some(Key::Num6)),
+    key("7", Code::Digit7, "7", "7", Some(Key::Num7)),
+    key("8", Code::Digit8, "8", "8", Some(Key::Num8)),
+    key("9", Code::Digit9, "9", "9", Some(Key::Num9)),
+    key("Minus", Code::Minus, "minus", "-", Some(Key::Minus)),
+    key("Equal", Code::Equal, "equal", "=", Some(Key::Equals)),
+    key("BracketLeft", Code::BracketLeft, "bracketleft", "[", Some(Key::OpenBracket)),
+    key("BracketRight", Code::BracketRight, "bracketright", "]", Some(Key::CloseBracket)),
+    key("Backslash", Code::Backslash, "backslash", "\\", Some(Key::Backslash)),
+    key("Semicolon", Code::Semicolon, "semicolon", ";", Some(Key::Semicolon)),
+    key("Quote", Code::Quote, "apostrophe", "'", Some(Key::Quote)),
+    key("Backquote", Code::Backquote, "grave", "`", Some(Key::Backtick)),
+    key("Comma", Code::Comma, "comma", ",", Some(Key::Comma)),
+    key("Period", Code::Period, "period", ".", Some(Key::Period)),
+    key("Slash", Code::Slash, "slash", "/", Some(Key::Slash)),
+    key("Space", Code::Space, "space", "Space", Some(Key::Space)),
+    key("Enter", Code::Enter, "Return", "Return", Some(Key::Enter)),
+    key("Tab", Code::Tab, "Tab", "Tab", Some(Key::Tab)),
+    key("Backspace", Code::Backspace, "BackSpace", "Backspace", Some(Key::Backspace)),
+    key("Escape", Code::Escape, "Escape", "Esc", Some(Key::Escape)),
+    key("Insert", Code::Insert, "Insert", "Ins", Some(Key::Insert)),
+    key("Delete", Code::Delete, "Delete", "Del", Some(Key::Delete)),
+    key("Home", Code::Home, "Home", "Home", Some(Key::Home)),
+    key("End", Code::End, "End", "End", Some(Key::End)),
+    key("PageUp", Code::PageUp, "Page_Up", "PgUp", Some(Key::PageUp)),
+    key("PageDown", Code::PageDown, "Page_Down", "PgDown", Some(Key::PageDown)),
+    key("Up", Code::ArrowUp, "Up", "Up", Some(Key::ArrowUp)),
+    key("Down", Code::ArrowDown, "Down", "Down", Some(Key::ArrowDown)),
+    key("Left", Code::ArrowLeft, "Left", "Left", Some(Key::ArrowLeft)),
+    key("Right", Code::ArrowRight, "Right", "Right", Some(Key::ArrowRight)),
+    key("PrintScreen", Code::PrintScreen, "Print", "Print", None),
+    key("ScrollLock", Code::ScrollLock, "Scroll_Lock", "ScrollLock", None),
+    key("Pause", Code::Pause, "Pause", "Pause", None),
+    key("Numpad0", Code::Numpad0, "KP_0", "Num+0", None),
+    key("Numpad1", Code::Numpad1, "KP_1", "Num+1", None),
+    key("Numpad2", Code::Numpad2, "KP_2", "Num+2", None),
+    key("Numpad3", Code::Numpad3, "KP_3", "Num+3", None),
+    key("Numpad4", Code::Numpad4, "KP_4", "Num+4", None),
+    key("Numpad5", Code::Numpad5, "KP_5", "Num+5", None),
+    key("Numpad6", Code::Numpad6, "KP_6", "Num+6", None),
+    key("Numpad7", Code::Numpad7, "KP_7", "Num+7", None),
+    key("Numpad8", Code::Numpad8, "KP_8", "Num+8", None),
+    key("Numpad9", Code::Numpad9, "KP_9", "Num+9", None),
+    key("NumpadAdd", Code::NumpadAdd, "KP_Add", "Num++", None),
+    key("NumpadSubtract", Code::NumpadSubtract, "KP_Subtract", "Num+-", None),
+    key("NumpadMultiply", Code::NumpadMultiply, "KP_Multiply", "Num+*", None),
+    key("NumpadDivide", Code::NumpadDivide, "KP_Divide", "Num+/", None),
+    key("NumpadDecimal", Code::NumpadDecimal, "KP_Decimal", "Num+.", None),
+    key("NumpadEnter", Code::NumpadEnter, "KP_Enter", "Num+Enter", None),
+    key("MediaPlay", Code::MediaPlay, "XF86AudioPlay", "Media Play", None),
+    key("MediaPause", Code::MediaPause, "XF86AudioPause", "Media Pause", None),
+    key("MediaStop", Code::MediaStop, "XF86AudioStop", "Media Stop", None),
+    key("MediaNext", Code::MediaTrackNext, "XF86AudioNext", "Media Next", None),
+    key("MediaPrevious", Code::MediaTrackPrevious, "XF86AudioPrev", "Media Previous", None),
+    key("VolumeUp", Code::AudioVolumeUp, "XF86AudioRaiseVolume", "Volume Up", None),
+    key("VolumeDown", Code::AudioVolumeDown, "XF86AudioLowerVolume", "Volume Down", None),
+    key("VolumeMute", Code::AudioVolumeMute, "XF86AudioMute", "Volume Mute", None),
+    key("F1", Code::F1, "F1", "F1", Some(Key::F1)),
+    key("F2", Code::F2, "F2", "F2", Some(Key::F2)),
+    key("F3", Code::F3, "F3", "F3", Some(Key::F3)),
+    key("F4", Code::F4, "F4", "F4", Some(Key::F4)),
+    key("F5", Code::F5, "F5", "F5", Some(Key::F5)),
+    key("F6", Code::F6, "F6", "F6", Some(Key::F6)),
+    key("F7", Code::F7, "F7", "F7", Some(Key::F7)),
+    key("F8", Code::F8, "F8", "F8", Some(Key::F8)),
+    key("F9", Code::F9, "F9", "F9", Some(Key::F9)),
+    key("F10", Code::F10, "F10", "F10", Some(Key::F10)),
+    key("F11", Code::F11, "F11", "F11", Some(Key::F11)),
+    key("F12", Code::F12, "F12", "F12", Some(Key::F12)),
+    key("F13", Code::F13, "F13", "F13", Some(Key::F13)),
+    key("F14", Code::F14, "F14", "F14", Some(Key::F14)),
+    key("F15", Code::F15, "F15", "F15", Some(Key::F15)),
+    key("F16", Code::F16, "F16", "F16", Some(Key::F16)),
+    key("F17", Code::F17, "F17", "F17", Some(Key::F17)),
+    key("F18", Code::F18, "F18", "F18", Some(Key::F18)),
+    key("F19", Code::F19, "F19", "F19", Some(Key::F19)),
+    key("F20", Code::F20, "F20", "F20", Some(Key::F20)),
+    key("F21", Code::F21, "F21", "F21", Some(Key::F21)),
+    key("F22", Code::F22, "F22", "F22", Some(Key::F22)),
+    key("F23", Code::F23, "F23", "F23", Some(Key::F23)),
+    key("F24", Code::F24, "F24", "F24", Some(Key::F24)),
+];
+
+pub fn lookup(name: &str) -> Option<&'static KeyInfo> {
+    KEYS.iter().find(|k| k.name == name)
+}
+
+pub fn from_egui(key: Key) -> Option<&'static KeyInfo> {
+    KEYS.iter().find(|k| k.egui == Some(key))
+}
+
+/// X keysym for a key name, passing unknown names through unchanged.
+pub fn keysym(name: &str) -> &str {
+    lookup(name).map_or(name, |k| k.keysym)
+}
+
+/// Qt key name for a key name, passing unknown names through unchanged.
+pub fn qt_name(name: &str) -> &str {
+    lookup(name).map_or(name, |k| k.qt)
+}
@@ -0,0 +1,297 @@
+use std::sync::Arc;
+
+use crate::keys;
+use crate::settings::HotkeyConfig;
+use crate::window_backend::CommandRunner;
+
+/// Name of the .desktop file kglobalaccel launches for the toggle shortcut.
+pub const SHORTCUT_DESKTOP_FILE: &str = "slickrun-toggle.desktop";
+
+/// Locate the SlickRun window from inside a KWin script.
+/// Plasma 6 uses `windowList()`, Plasma 5 still exposes `clientList()`.
+const FIND_WINDOW_JS: &str = r#"const slickrunWindows = workspace.windowList ? workspace.windowList() : workspace.clientList();
+const win = slickrunWindows.find(w => w.caption === 'SlickRun');
+"#;
+
+fn script_dir() -> std::path::PathBuf {
+    std::env::var("XDG_RUNTIME_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::path::PathBuf::from("/tmp"))
+}
+
+/// Parse the script id from a `loadScript` reply, e.g. "(3,)" or "(int32 3,)".
+fn parse_script_id(reply: &str) -> Option<i32> {
+    reply
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .trim_end_matches(',')
+        .trim()
+        .trim_start_matches("int32")
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn kwin_call(runner: &dyn CommandRunner, object_path: &str, method: &str, args: &[&str]) -> Option<String> {
+    let mut argv = vec![
+        "call", "--session",
+        "--dest", "org.kde.KWin",
+        "--object-path", object_path,
+        "--method", method,
+    ];
+    argv.extend_from_slice(args);
+    runner.output("gdbus", &argv)
+}
+
+/// Load a one-shot KWin script, run it, and unload it again.
+/// KWin scripts are the only way to activate or move windows on Plasma Wayland.
+fn run_script(runner: &dyn CommandRunner, name: &str, source: &str) -> Result<(), String> {
+    let path = script_dir().join(format!("{}.js", name));
+    std::fs::write(&path, source).map_err(|e| e.to_string())?;
+    let path_str = path.to_string_lossy().to_string();
+
+    // A previous run may have left the script loaded — loadScript refuses duplicates.
+    kwin_call(runner, "/Scripting", "org.kde.kwin.Scripting.unloadScript", &[name]);
+
+    let reply = kwin_call(
+        runner,
+        "/Scripting",
+        "org.kde.kwin.Scripting.loadScript",
+        &[&path_str, name],
+    )
+    .ok_or("loadScript failed (is KWin running?)")?;
+    let id = parse_script_id(&reply).ok_or_else(|| format!("unexpected loadScript reply: {}", reply.trim()))?;
+
+    // Plasma 6 exposes scripts at /Scripting/Script<id>, Plasma 5 at /<id>.
+    let ran = kwin_call(runner, &format!("/Scripting/Script{}", id), "org.kde.kwin.Script.run", &[])
+        .or_else(|| kwin_call(runner, &format!("/{}", id), "org.kde.kwin.Script.run", &[]));
+
+    kwin_call(runner, "/Scripting", "org.kde.kwin.Scripting.unloadScript", &[name]);
+    let _ = std::fs::remove_file(&path);
+
+    ran.map(|_| ()).ok_or_else(|| format!("failed to run KWin script {}", id))
+}
+
+/// Run a KWin script on a background thread so the UI never waits on D-Bus.
+fn run_script_async(runner: Arc<dyn CommandRunner>, name: &'static str, source: String) {
+    std::thread::spawn(move || {
+        if let Err(e) = run_script(runner.as_ref(), name, &source) {
+            eprintln!("[SlickRun] KWin script {} failed: {e}", name);
+        }
+    });
+}
+
+/// Activate (un-minimize, raise, focus) the SlickRun window via KWin.
+pub fn activate_window(runner: Arc<dyn CommandRunner>) {
+    let source = format!(
+        r#"{}if (win) {{
+    win.minimized = false;
+    if (workspace.windowList) {{
+        workspace.activeWindow = win;
+    }} else {{
+        workspace.activeClient = win;
+    }}
+}}
+"#,
+        FIND_WINDOW_JS
+    );
+    run_script_async(runner, "slickrun-activate", source);
+}
+
+/// Toggle KWin's "keep above others" for the SlickRun window.
+pub fn set_keep_above(runner: Arc<dyn CommandRunner>, enable: bool) {
+    let source = format!("{}if (win) {{\n    win.keepAbove = {};\n}}\n", FIND_WINDOW_JS, enable);
+    run_script_async(runner, "slickrun-keep-above", source);
+}
+
+/// Move the SlickRun window via KWin (frame geometry at compositor level).
+pub fn move_window(runner: Arc<dyn CommandRunner>, x: i32, y: i32) {
+    let source = format!(
+        r#"{}if (win) {{
+    const g = win.frameGeometry;
+    win.frameGeometry = {{ x: {}, y: {}, width: g.width, height: g.height }};
+}}
+"#,
+        FIND_WINDOW_JS, x, y
+    );
+    run_script_async(runner, "slickrun-move", source);
+    eprintln!("[SlickRun] Moving window via KWin to ({}, {})", x, y);
+}
+
+/// Convert HotkeyConfig to Qt key sequence format, e.g. "Meta+Shift+Q"
+pub fn hotkey_to_kde_binding(hotkey: &HotkeyConfig) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    if hotkey.super_key {
+        parts.push("Meta");
+    }
+    if hotkey.ctrl {
+        parts.push("Ctrl");
+    }
+    if hotkey.alt {
+        parts.push("Alt");
+    }
+    if hotkey.shift {
+        parts.push("Shift");
+    }
+    parts.push(keys::qt_name(&hotkey.key));
+    parts.join("+")
+}
+
+/// Qt::Key code for a key's Qt name, e.g. 0x51 for "Q" or 0x01000030 for "F1".
+fn qt_key(qt: &str) -> Option<i32> {
+    // Qt::KeypadModifier marks the numpad variants
+    if let Some(key) = qt.strip_prefix("Num+") {
+        let code = if key == "Enter" { 0x0100_0005 } else { qt_key(key)? };
+        return Some(0x2000_0000 | code);
+    }
+    if let Some(n) = qt.strip_prefix('F').and_then(|n| n.parse::<i32>().ok()) {
+        return Some(0x0100_0030 + n - 1);
+    }
+    // Letters, digits and punctuation use their (uppercase) ASCII code
+    if let [c] = qt.as_bytes() {
+        return Some(*c as i32);
+    }
+    Some(match qt {
+        "Space" => 0x20,
+        "Esc" => 0x0100_0000,
+        "Tab" => 0x0100_0001,
+        "Backspace" => 0x0100_0003,
+        "Return" => 0x0100_0004,
+        "Ins" => 0x0100_0006,
+        "Del" => 0x0100_0007,
+        "Pause" => 0x0100_0008,
+        "Print" => 0x0100_0009,
+        "Home" => 0x0100_0010,
+        "End" => 0x0100_0011,
+        "Left" => 0x0100_0012,
+        "Up" => 0x0100_0013,
+        "Right" => 0x0100_0014,
+        "Down" => 0x0100_0015,
+        "PgUp" => 0x0100_0016,
+        "PgDown" => 0x0100_0017,
+        "ScrollLock" => 0x0100_0026,
+        "Volume Down" => 0x0100_0070,
+        "Volume Mute" => 0x0100_0071,
+        "Volume Up" => 0x0100_0072,
+        "Media Play" => 0x0100_0080,
+        "Media Stop" => 0x0100_0081,
+        "Media Previous" => 0x0100_0082,
+        "Media Next" => 0x0100_0083,
+        "Media Pause" => 0x0100_0085,
+        _ => return None,
+    })
+}
+
+/// The hotkey as kglobalaccel stores it: a Qt::Key code or'ed with
+/// Qt::KeyboardModifier flags.
+fn hotkey_to_qt_keycode(hotkey: &HotkeyConfig) -> Option<i32> {
+    let mut code = qt_key(keys::qt_name(&hotkey.key))?;
+    for (held, flag) in [
+        (hotkey.shift, 0x0200_0000),
+        (hotkey.ctrl, 0x0400_0000),
+        (hotkey.alt, 0x0800_0000),
+        (hotkey.super_key, 0x1000_0000),
+    ] {
+        if held {
+            code |= flag;
+        }
+    }
+    Some(code)
+}
+
+fn kglobalaccel_call(runner: &dyn CommandRunner, method: &str, args: &[&str]) -> Option<String> {
+    let mut argv = vec![
+        "call", "--session",
+        "--dest", "org.kde.kglobalaccel",
+        "--object-path", "/kglobalaccel",
+        "--method", method,
+    ];
+    argv.extend_from_slice(args);
+    runner.output("gdbus", &argv)
+}
+
+/// Register a Plasma global shortcut through kglobalaccel.
+/// A component named after a .desktop file is a "service": kglobalaccel
+/// launches the file when its `_launch` action fires. Ours writes to the
+/// toggle FIFO, and `show()` activates the window via KWin. The binding is
+/// set over D-Bus, so it works right away and kglobalaccel persists it.
+pub fn register_shortcut(runner: &dyn CommandRunner, hotkey: &HotkeyConfig, pipe_path: &std::path::Path) {
+    let Some(data_dir) = dirs::data_dir() else {
+        eprintln!("[SlickRun] No data directory — cannot register KDE shortcut");
+        return;
+    };
+    let apps_dir = data_dir.join("applications");
+    let _ = std::fs::create_dir_all(&apps_dir);
+
+    let desktop = format!(
+        "[Desktop Entry]\nType=Application\nName=SlickRun Toggle\nExec=sh -c 'echo t > {}'\nNoDisplay=true\nStartupNotify=false\nX-KDE-GlobalAccel-CommandShortcut=true\n",
+        pipe_path.display()
+    );
+    let _ = std::fs::write(apps_dir.join(SHORTCUT_DESKTOP_FILE), desktop);
+
+    let binding = hotkey_to_kde_binding(hotkey);
+    let Some(keycode) = hotkey_to_qt_keycode(hotkey) else {
+        eprintln!("[SlickRun] {} has no Qt key code — cannot register KDE shortcut", binding);
+        return;
+    };
+    // [component, action, component friendly name, action friendly name]
+    let action_id = format!("['{}', '_launch', 'SlickRun', 'Toggle SlickRun']", SHORTCUT_DESKTOP_FILE);
+    let registered = kglobalaccel_call(runner, "org.kde.KGlobalAccel.doRegister", &[&action_id])
+        .and_then(|_| {
+            kglobalaccel_call(
+                runner,
+                "org.kde.KGlobalAccel.setForeignShortcut",
+                &[&action_id, &format!("[{}]", keycode)],
+            )
+        });
+    if registered.is_none() {
+        eprintln!("[SlickRun] kglobalaccel did not accept {} — cannot register KDE shortcut", binding);
+        return;
+    }
+
+    eprintln!("[SlickRun] Registered KDE shortcut: {} -> FIFO", binding);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hotkey(super_key: bool, ctrl: bool, shift: bool, alt: bool, key: &str) -> HotkeyConfig {
+        HotkeyConfig {
+            super_key,
+            ctrl,
+            shift,
+            alt,
+            key: key.into(),
+        }
+    }
+
+    #[test]
+    fn qt_keycodes_combine_key_and_modifiers() {
+        assert_eq!(
+            hotkey_to_qt_keycode(&hotkey(true, false, true, false, "Q")),
+            Some(0x1200_0051)
+        );
+        assert_eq!(
+            hotkey_to_qt_keycode(&hotkey(false, true, false, true, "F5")),
+            Some(0x0D00_0034)
+        );
+        assert_eq!(
+            hotkey_to_qt_keycode(&hotkey(false, false, false, true, "Space")),
+            Some(0x0800_0020)
+        );
+        assert_eq!(
+            hotkey_to_qt_keycode(&hotkey(false, true, false, false, "Comma")),
+            Some(0x0400_002C)
+        );
+        assert_eq!(
+            hotkey_to_qt_keycode(&hotkey(true, false, false, false, "NumpadEnter")),
+            Some(0x3100_0005)
+        );
+        assert_eq!(
+            hotkey_to_qt_keycode(&hotkey(true, false, false, false, "Numpad7")),
+            Some(0x3000_0037)
+        );
+    }
+}
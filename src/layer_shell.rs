@@ -0,0 +1,822 @@
+use std::ffi::{CStr, c_char};
+use std::num::NonZeroU32;
+use std::os::fd::OwnedFd;
+use std::ptr::NonNull;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use eframe::{egui, egui_glow, glow};
+use glutin::config::{Config, ConfigTemplateBuilder};
+use glutin::context::{
+    ContextAttributesBuilder, NotCurrentGlContext, PossiblyCurrentContext, PossiblyCurrentGlContext,
+};
+use glutin::display::{Display, DisplayApiPreference, GlDisplay};
+use glutin::surface::{GlSurface, Surface, SurfaceAttributesBuilder, SwapInterval, WindowSurface};
+use raw_window_handle::{RawDisplayHandle, RawWindowHandle, WaylandDisplayHandle, WaylandWindowHandle};
+use smithay_client_toolkit::compositor::{CompositorHandler, CompositorState};
+use smithay_client_toolkit::output::{OutputHandler, OutputState};
+use smithay_client_toolkit::reexports::calloop::{self, EventLoop};
+use smithay_client_toolkit::reexports::calloop_wayland_source::WaylandSource;
+use smithay_client_toolkit::reexports::client::globals::registry_queue_init;
+use smithay_client_toolkit::reexports::client::protocol::{wl_keyboard, wl_output, wl_pointer, wl_seat, wl_surface};
+use smithay_client_toolkit::reexports::client::{Connection, Dispatch, Proxy, QueueHandle, WEnum};
+use smithay_client_toolkit::registry::{ProvidesRegistryState, RegistryState};
+use smithay_client_toolkit::seat::pointer::{PointerEvent, PointerEventKind, PointerHandler};
+use smithay_client_toolkit::seat::{Capability, SeatHandler, SeatState};
+use smithay_client_toolkit::shell::WaylandSurface;
+use smithay_client_toolkit::shell::wlr_layer::{
+    Anchor, KeyboardInteractivity, Layer, LayerShell, LayerShellHandler, LayerSurface, LayerSurfaceConfigure,
+};
+use smithay_client_toolkit::{
+    delegate_compositor, delegate_layer, delegate_output, delegate_pointer, delegate_registry, delegate_seat,
+    registry_handlers,
+};
+use xkbcommon_dl::{self as xkb, XkbCommon};
+
+use crate::app::LauncherApp;
+use crate::desktop::Desktop;
+use crate::keys;
+use crate::settings::PlacementPreset;
+
+const BTN_LEFT: u32 = 0x110;
+const BTN_RIGHT: u32 = 0x111;
+const BTN_MIDDLE: u32 = 0x112;
+
+/// Run the launcher as a wlr-layer-shell overlay instead of an eframe window.
+/// wlroots compositors only let a client place, raise and focus a surface
+/// through the layer shell, so we drive egui ourselves: the overlay layer
+/// keeps it above normal windows, exclusive keyboard interactivity gives it
+/// the keyboard, and anchors put it where the placement preset asks.
+/// Hiding destroys the surface; showing creates a new one.
+pub fn run(tray: tray_icon::TrayIcon, desktop: Desktop) -> Result<(), String> {
+    let conn = Connection::connect_to_env().map_err(|e| format!("Wayland connection failed: {e}"))?;
+    let (globals, event_queue) = registry_queue_init(&conn).map_err(|e| format!("Wayland registry failed: {e}"))?;
+    let qh = event_queue.handle();
+
+    let compositor = CompositorState::bind(&globals, &qh).map_err(|e| format!("wl_compositor: {e}"))?;
+    let layer_shell =
+        LayerShell::bind(&globals, &qh).map_err(|_| "the compositor has no wlr-layer-shell".to_string())?;
+
+    let mut event_loop: EventLoop<Overlay> = EventLoop::try_new().map_err(|e| format!("Event loop failed: {e}"))?;
+    WaylandSource::new(conn.clone(), event_queue)
+        .insert(event_loop.handle())
+        .map_err(|e| format!("Wayland event source failed: {e}"))?;
+
+    // egui asks for repaints from other threads (tray, FIFO listener), so
+    // keep the earliest deadline and wake the loop to recompute its timeout
+    let (ping, ping_source) = calloop::ping::make_ping().map_err(|e| format!("Ping source failed: {e}"))?;
+    event_loop
+        .handle()
+        .insert_source(ping_source, |_, _, _| {})
+        .map_err(|e| format!("Ping source failed: {e}"))?;
+    let repaint_at = Arc::new(Mutex::new(Some(Instant::now())));
+
+    let ctx = egui::Context::default();
+    {
+        let repaint_at = repaint_at.clone();
+        ctx.set_request_repaint_callback(move |info| {
+            if let Some(when) = Instant::now().checked_add(info.delay) {
+                let mut repaint_at = repaint_at.lock().unwrap();
+                *repaint_at = Some(repaint_at.map_or(when, |at| at.min(when)));
+            }
+            ping.ping();
+        });
+    }
+    let app = LauncherApp::new(&ctx, tray, desktop);
+    let size = app.settings().appearance().size();
+
+    let mut overlay = Overlay {
+        registry_state: RegistryState::new(&globals),
+        output_state: OutputState::new(&globals, &qh),
+        seat_state: SeatState::new(&globals, &qh),
+        compositor,
+        layer_shell,
+        conn,
+        qh,
+        ctx,
+        app,
+        start: Instant::now(),
+        repaint_at,
+        exit: false,
+        size: (size.0 as u32, size.1 as u32),
+        scale: 1,
+        layer: None,
+        configured: false,
+        egl_surface: None,
+        gl: None,
+        pending_textures: egui::TexturesDelta::default(),
+        needs_paint: false,
+        events: Vec::new(),
+        focused: false,
+        modifiers: egui::Modifiers::NONE,
+        keyboard: None,
+        pointer: None,
+        xkb: None,
+        repeat_rate: 25,
+        repeat_delay: Duration::from_millis(600),
+        repeating: None,
+    };
+    overlay.show_surface();
+
+    while !overlay.exit {
+        let timeout = overlay
+            .next_wakeup()
+            .map(|at| at.saturating_duration_since(Instant::now()));
+        event_loop
+            .dispatch(timeout, &mut overlay)
+            .map_err(|e| format!("Event loop failed: {e}"))?;
+        overlay.repeat_key();
+        if overlay.frame_due() {
+            overlay.frame();
+        }
+    }
+    Ok(())
+}
+
+/// The keymap and modifier state of the seat's keyboard.
+struct Xkb {
+    lib: &'static XkbCommon,
+    context: *mut xkb::xkb_context,
+    keymap: *mut xkb::xkb_keymap,
+    state: *mut xkb::xkb_state,
+}
+
+impl Xkb {
+    /// Compile the keymap the compositor sent as a file descriptor.
+    fn from_fd(fd: OwnedFd, size: usize) -> Result<Self, String> {
+        let lib = xkb::xkbcommon_option().ok_or("libxkbcommon.so could not be loaded")?;
+        let map = unsafe { memmap2::MmapOptions::new().len(size).map_copy_read_only(&fd) }
+            .map_err(|e| format!("keymap mmap failed: {e}"))?;
+        // The keymap is NUL-terminated text
+        let len = map.iter().position(|&b| b == 0).unwrap_or(map.len());
+        unsafe {
+            let context = (lib.xkb_context_new)(xkb::xkb_context_flags::XKB_CONTEXT_NO_FLAGS);
+            if context.is_null() {
+                return Err("xkb_context_new failed".into());
+            }
+            let keymap = (lib.xkb_keymap_new_from_buffer)(
+                context,
+                map.as_ptr() as *const c_char,
+                len,
+                xkb::xkb_keymap_format::XKB_KEYMAP_FORMAT_TEXT_V1,
+                xkb::xkb_keymap_compile_flags::XKB_KEYMAP_COMPILE_NO_FLAGS,
+            );
+            if keymap.is_null() {
+                (lib.xkb_context_unref)(context);
+                return Err("the compositor's keymap did not compile".into());
+            }
+            let state = (lib.xkb_state_new)(keymap);
+            if state.is_null() {
+                (lib.xkb_keymap_unref)(keymap);
+                (lib.xkb_context_unref)(context);
+                return Err("xkb_state_new failed".into());
+            }
+            Ok(Xkb {
+                lib,
+                context,
+                keymap,
+                state,
+            })
+        }
+    }
+
+    fn update_mask(&self, depressed: u32, latched: u32, locked: u32, group: u32) {
+        unsafe {
+            (self.lib.xkb_state_update_mask)(self.state, depressed, latched, locked, 0, 0, group);
+        }
+    }
+
+    fn modifiers(&self) -> egui::Modifiers {
+        let active = |name: &CStr| unsafe {
+            (self.lib.xkb_state_mod_name_is_active)(
+                self.state,
+                name.as_ptr(),
+                xkb::xkb_state_component::XKB_STATE_MODS_EFFECTIVE,
+            ) > 0
+        };
+        let ctrl = active(c"Control");
+        egui::Modifiers {
+            alt: active(c"Mod1"),
+            ctrl,
+            shift: active(c"Shift"),
+            mac_cmd: false,
+            command: ctrl,
+        }
+    }
+
+    /// Keysym name of the key, e.g. "Return" or "a"
+    fn keysym_name(&self, keycode: u32) -> String {
+        let mut buf = [0 as c_char; 64];
+        unsafe {
+            let sym = (self.lib.xkb_state_key_get_one_sym)(self.state, keycode);
+            (self.lib.xkb_keysym_get_name)(sym, buf.as_mut_ptr(), buf.len());
+            CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
+        }
+    }
+
+    fn text(&self, keycode: u32) -> String {
+        let mut buf = [0 as c_char; 64];
+        unsafe {
+            (self.lib.xkb_state_key_get_utf8)(self.state, keycode, buf.as_mut_ptr(), buf.len());
+            CStr::from_ptr(buf.as_ptr()).to_string_lossy().into_owned()
+        }
+    }
+
+    fn repeats(&self, keycode: u32) -> bool {
+        unsafe { (self.lib.xkb_keymap_key_repeats)(self.keymap, keycode) != 0 }
+    }
+}
+
+impl Drop for Xkb {
+    fn drop(&mut self) {
+        unsafe {
+            (self.lib.xkb_state_unref)(self.state);
+            (self.lib.xkb_keymap_unref)(self.keymap);
+            (self.lib.xkb_context_unref)(self.context);
+        }
+    }
+}
+
+/// The egui key for an X keysym name. Shift turns "a" into "A", so compare
+/// without case.
+fn egui_key(keysym: &str) -> Option<egui::Key> {
+    let keysym = match keysym {
+        "ISO_Left_Tab" => "Tab",
+        "KP_Enter" => "Return",
+        other => other,
+    };
+    keys::KEYS
+        .iter()
+        .find(|k| k.keysym.eq_ignore_ascii_case(keysym))
+        .and_then(|k| k.egui)
+}
+
+/// The GL context and egui painter. Created with the first surface and kept
+/// while hidden, so showing again doesn't re-upload the font atlas.
+struct Gl {
+    display: Display,
+    config: Config,
+    context: PossiblyCurrentContext,
+    painter: egui_glow::Painter,
+}
+
+struct Overlay {
+    registry_state: RegistryState,
+    output_state: OutputState,
+    seat_state: SeatState,
+    compositor: CompositorState,
+    layer_shell: LayerShell,
+    conn: Connection,
+    qh: QueueHandle<Overlay>,
+
+    ctx: egui::Context,
+    app: LauncherApp,
+    start: Instant,
+    // When egui next wants a frame
+    repaint_at: Arc<Mutex<Option<Instant>>>,
+    exit: bool,
+
+    // Logical size the app asked for
+    size: (u32, u32),
+    scale: i32,
+    // None while hidden
+    layer: Option<LayerSurface>,
+    // Whether the compositor has sized `layer` yet; we may not draw before
+    configured: bool,
+    egl_surface: Option<Surface<WindowSurface>>,
+    gl: Option<Gl>,
+    // Texture changes from frames run while there was nothing to paint on
+    pending_textures: egui::TexturesDelta,
+    needs_paint: bool,
+
+    events: Vec<egui::Event>,
+    focused: bool,
+    modifiers: egui::Modifiers,
+    keyboard: Option<wl_keyboard::WlKeyboard>,
+    pointer: Option<wl_pointer::WlPointer>,
+    xkb: Option<Xkb>,
+    // Key repeat, from the compositor's repeat_info
+    repeat_rate: u32,
+    repeat_delay: Duration,
+    // Keycode being held and when it next repeats
+    repeating: Option<(u32, Instant)>,
+}
+
+impl Overlay {
+    fn next_wakeup(&self) -> Option<Instant> {
+        let repaint = *self.repaint_at.lock().unwrap();
+        let repeat = self.repeating.map(|(_, at)| at);
+        match (repaint, repeat) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    fn frame_due(&self) -> bool {
+        let repaint_due = self.repaint_at.lock().unwrap().is_some_and(|at| at <= Instant::now());
+        repaint_due || self.needs_paint || !self.events.is_empty()
+    }
+
+    /// Map a new layer surface. Without anchors the compositor centers it.
+    fn show_surface(&mut self) {
+        if self.layer.is_some() {
+            return;
+        }
+        let surface = self.compositor.create_surface(&self.qh);
+        let layer = self
+            .layer_shell
+            .create_layer_surface(&self.qh, surface, Layer::Overlay, Some("slickrun"), None);
+        let settings = self.app.settings();
+        let margin = settings.placement_margin as i32;
+        match settings.placement_preset {
+            PlacementPreset::TopCenter => {
+                layer.set_anchor(Anchor::TOP);
+                layer.set_margin(margin, 0, 0, 0);
+            }
+            PlacementPreset::BottomCenter => {
+                layer.set_anchor(Anchor::BOTTOM);
+                layer.set_margin(0, 0, margin, 0);
+            }
+            _ => {}
+        }
+        layer.set_keyboard_interactivity(KeyboardInteractivity::Exclusive);
+        layer.set_size(self.size.0, self.size.1);
+        // The first commit has no buffer; the compositor answers with a configure
+        layer.commit();
+        self.layer = Some(layer);
+        self.configured = false;
+    }
+
+    fn hide_surface(&mut self) {
+        // The EGL window must go before the wl_surface it wraps
+        self.egl_surface = None;
+        self.layer = None;
+        self.configured = false;
+        self.focused = false;
+        self.repeating = None;
+    }
+
+    fn physical_size(&self) -> (u32, u32) {
+        let scale = self.scale.max(1) as u32;
+        (self.size.0.max(1) * scale, self.size.1.max(1) * scale)
+    }
+
+    /// Create (or resize) the EGL surface for the configured layer surface.
+    fn attach_gl(&mut self) -> Result<(), String> {
+        let Some(layer) = &self.layer else {
+            return Ok(());
+        };
+        let (width, height) = self.physical_size();
+        let (width, height) = (NonZeroU32::new(width).unwrap(), NonZeroU32::new(height).unwrap());
+        if let (Some(surface), Some(gl)) = (&self.egl_surface, &self.gl) {
+            surface.resize(&gl.context, width, height);
+            return Ok(());
+        }
+
+        let wl_surface = NonNull::new(layer.wl_surface().id().as_ptr() as *mut _).ok_or("null wl_surface")?;
+        let window = RawWindowHandle::Wayland(WaylandWindowHandle::new(wl_surface));
+        let attrs = SurfaceAttributesBuilder::<WindowSurface>::new().build(window, width, height);
+
+        if self.gl.is_none() {
+            self.gl = Some(self.create_gl(window, &attrs)?);
+            return Ok(());
+        }
+        let gl = self.gl.as_ref().unwrap();
+        let surface = unsafe { gl.display.create_window_surface(&gl.config, &attrs) }
+            .map_err(|e| format!("EGL surface failed: {e}"))?;
+        gl.context
+            .make_current(&surface)
+            .map_err(|e| format!("EGL make current failed: {e}"))?;
+        let _ = surface.set_swap_interval(&gl.context, SwapInterval::DontWait);
+        self.egl_surface = Some(surface);
+        Ok(())
+    }
+
+    fn create_gl(
+        &mut self,
+        window: RawWindowHandle,
+        attrs: &glutin::surface::SurfaceAttributes<WindowSurface>,
+    ) -> Result<Gl, String> {
+        let wl_display = NonNull::new(self.conn.backend().display_ptr() as *mut _).ok_or("null wl_display")?;
+        let display = unsafe {
+            Display::new(
+                RawDisplayHandle::Wayland(WaylandDisplayHandle::new(wl_display)),
+                DisplayApiPreference::Egl,
+            )
+        }
+        .map_err(|e| format!("EGL display failed: {e}"))?;
+        let template = ConfigTemplateBuilder::new()
+            .with_alpha_size(8)
+            .compatible_with_native_window(window)
+            .build();
+        let config = unsafe { display.find_configs(template) }
+            .map_err(|e| format!("EGL config failed: {e}"))?
+            .next()
+            .ok_or("no EGL config with an alpha channel")?;
+        let context = unsafe { display.create_context(&config, &ContextAttributesBuilder::new().build(Some(window))) }
+            .map_err(|e| format!("GL context failed: {e}"))?;
+        let surface =
+            unsafe { display.create_window_surface(&config, attrs) }.map_err(|e| format!("EGL surface failed: {e}"))?;
+        let context = context
+            .make_current(&surface)
+            .map_err(|e| format!("EGL make current failed: {e}"))?;
+        let _ = surface.set_swap_interval(&context, SwapInterval::DontWait);
+        self.egl_surface = Some(surface);
+
+        let glow = unsafe { glow::Context::from_loader_function_cstr(|name| display.get_proc_address(name)) };
+        let painter = egui_glow::Painter::new(Arc::new(glow), "", None, false)
+            .map_err(|e| format!("egui painter failed: {e}"))?;
+        Ok(Gl {
+            display,
+            config,
+            context,
+            painter,
+        })
+    }
+
+    fn raw_input(&mut self) -> egui::RawInput {
+        let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(self.size.0 as f32, self.size.1 as f32));
+        let mut raw_input = egui::RawInput {
+            screen_rect: Some(rect),
+            max_texture_side: self.gl.as_ref().map(|gl| gl.painter.max_texture_side()),
+            time: Some(self.start.elapsed().as_secs_f64()),
+            modifiers: self.modifiers,
+            events: std::mem::take(&mut self.events),
+            focused: self.focused,
+            ..Default::default()
+        };
+        let viewport = raw_input.viewports.entry(egui::ViewportId::ROOT).or_default();
+        viewport.native_pixels_per_point = Some(self.scale as f32);
+        viewport.inner_rect = Some(rect);
+        viewport.focused = Some(self.focused);
+        viewport.minimized = Some(self.layer.is_none());
+        raw_input
+    }
+
+    /// Run the app for one frame, apply what it asked of the window and paint.
+    fn frame(&mut self) {
+        *self.repaint_at.lock().unwrap() = None;
+        self.needs_paint = false;
+        let raw_input = self.raw_input();
+        let app = &mut self.app;
+        let output = self.ctx.run(raw_input, |ctx| app.run_frame(ctx));
+
+        for command in &output.platform_output.commands {
+            if let egui::OutputCommand::CopyText(text) = command
+                && let Ok(mut clipboard) = arboard::Clipboard::new()
+            {
+                let _ = clipboard.set_text(text.clone());
+            }
+        }
+
+        if let Some(viewport) = output.viewport_output.get(&egui::ViewportId::ROOT) {
+            for command in &viewport.commands {
+                match command {
+                    egui::ViewportCommand::Close => self.exit = true,
+                    egui::ViewportCommand::Minimized(true) => self.hide_surface(),
+                    egui::ViewportCommand::Minimized(false) => self.show_surface(),
+                    egui::ViewportCommand::InnerSize(size) => {
+                        self.size = (size.x.round() as u32, size.y.round() as u32);
+                        if let Some(layer) = &self.layer {
+                            layer.set_size(self.size.0, self.size.1);
+                            layer.commit();
+                        }
+                    }
+                    // Position, focus and stacking come from the layer surface itself
+                    _ => {}
+                }
+            }
+        }
+
+        self.pending_textures.append(output.textures_delta);
+        if !self.configured || self.exit {
+            return;
+        }
+        let (width, height) = self.physical_size();
+        let (Some(surface), Some(gl)) = (&self.egl_surface, &mut self.gl) else {
+            return;
+        };
+        let ppp = output.pixels_per_point;
+        let clipped = self.ctx.tessellate(output.shapes, ppp);
+        let visuals = self.ctx.style().visuals.clone();
+        gl.painter
+            .clear([width, height], eframe::App::clear_color(&self.app, &visuals));
+        let textures = std::mem::take(&mut self.pending_textures);
+        gl.painter
+            .paint_and_update_textures([width, height], ppp, &clipped, &textures);
+        if let Err(e) = surface.swap_buffers(&gl.context) {
+            eprintln!("[SlickRun] Layer shell: swap failed: {}", e);
+        }
+    }
+
+    fn key(&mut self, keycode: u32, pressed: bool, repeat: bool) {
+        let Some(xkb) = &self.xkb else {
+            return;
+        };
+        let modifiers = self.modifiers;
+        let key = egui_key(&xkb.keysym_name(keycode));
+        let text = xkb.text(keycode);
+        if pressed && !repeat {
+            self.repeating =
+                (self.repeat_rate > 0 && xkb.repeats(keycode)).then(|| (keycode, Instant::now() + self.repeat_delay));
+        } else if !pressed && self.repeating.is_some_and(|(held, _)| held == keycode) {
+            self.repeating = None;
+        }
+
+        if let Some(key) = key {
+            if pressed && modifiers.command {
+                match key {
+                    egui::Key::C => self.events.push(egui::Event::Copy),
+                    egui::Key::X => self.events.push(egui::Event::Cut),
+                    egui::Key::V => {
+                        if let Some(text) = arboard::Clipboard::new().ok().and_then(|mut c| c.get_text().ok()) {
+                            self.events.push(egui::Event::Paste(text));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            self.events.push(egui::Event::Key {
+                key,
+                physical_key: None,
+                pressed,
+                repeat,
+                modifiers,
+            });
+        }
+        if pressed && !modifiers.ctrl && !modifiers.alt && !text.is_empty() && !text.chars().any(char::is_control) {
+            self.events.push(egui::Event::Text(text));
+        }
+    }
+
+    fn repeat_key(&mut self) {
+        let Some((keycode, at)) = self.repeating else {
+            return;
+        };
+        if at > Instant::now() {
+            return;
+        }
+        self.repeating = Some((keycode, at + Duration::from_secs(1) / self.repeat_rate));
+        self.key(keycode, true, true);
+    }
+}
+
+impl CompositorHandler for Overlay {
+    fn scale_factor_changed(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        surface: &wl_surface::WlSurface,
+        new_factor: i32,
+    ) {
+        self.scale = new_factor;
+        surface.set_buffer_scale(new_factor);
+        if self.configured
+            && let Err(e) = self.attach_gl()
+        {
+            eprintln!("[SlickRun] Layer shell: {}", e);
+        }
+        self.needs_paint = true;
+    }
+
+    fn transform_changed(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &wl_surface::WlSurface,
+        _: wl_output::Transform,
+    ) {
+    }
+
+    fn frame(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &wl_surface::WlSurface, _: u32) {}
+
+    fn surface_enter(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &wl_surface::WlSurface,
+        _: &wl_output::WlOutput,
+    ) {
+    }
+
+    fn surface_leave(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &wl_surface::WlSurface,
+        _: &wl_output::WlOutput,
+    ) {
+    }
+}
+
+impl OutputHandler for Overlay {
+    fn output_state(&mut self) -> &mut OutputState {
+        &mut self.output_state
+    }
+
+    fn new_output(&mut self, _: &Connection, _: &QueueHandle<Self>, _: wl_output::WlOutput) {}
+
+    fn update_output(&mut self, _: &Connection, _: &QueueHandle<Self>, _: wl_output::WlOutput) {}
+
+    fn output_destroyed(&mut self, _: &Connection, _: &QueueHandle<Self>, _: wl_output::WlOutput) {}
+}
+
+impl LayerShellHandler for Overlay {
+    fn closed(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &LayerSurface) {
+        // E.g. its output went away; the next show maps a new one
+        eprintln!("[SlickRun] Layer shell: the compositor closed our surface");
+        self.hide_surface();
+    }
+
+    fn configure(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &LayerSurface,
+        configure: LayerSurfaceConfigure,
+        _: u32,
+    ) {
+        let (width, height) = configure.new_size;
+        if width > 0 && height > 0 {
+            self.size = (width, height);
+        }
+        self.configured = true;
+        if let Err(e) = self.attach_gl() {
+            eprintln!("[SlickRun] Layer shell: {}", e);
+            self.exit = true;
+        }
+        self.needs_paint = true;
+    }
+}
+
+impl SeatHandler for Overlay {
+    fn seat_state(&mut self) -> &mut SeatState {
+        &mut self.seat_state
+    }
+
+    fn new_seat(&mut self, _: &Connection, _: &QueueHandle<Self>, _: wl_seat::WlSeat) {}
+
+    fn new_capability(
+        &mut self,
+        _: &Connection,
+        qh: &QueueHandle<Self>,
+        seat: wl_seat::WlSeat,
+        capability: Capability,
+    ) {
+        if capability == Capability::Keyboard && self.keyboard.is_none() {
+            self.keyboard = Some(seat.get_keyboard(qh, ()));
+        }
+        if capability == Capability::Pointer && self.pointer.is_none() {
+            self.pointer = self.seat_state.get_pointer(qh, &seat).ok();
+        }
+    }
+
+    fn remove_capability(&mut self, _: &Connection, _: &QueueHandle<Self>, _: wl_seat::WlSeat, capability: Capability) {
+        if capability == Capability::Keyboard
+            && let Some(keyboard) = self.keyboard.take()
+        {
+            keyboard.release();
+            self.xkb = None;
+            self.repeating = None;
+        }
+        if capability == Capability::Pointer
+            && let Some(pointer) = self.pointer.take()
+        {
+            pointer.release();
+        }
+    }
+
+    fn remove_seat(&mut self, _: &Connection, _: &QueueHandle<Self>, _: wl_seat::WlSeat) {}
+}
+
+impl Dispatch<wl_keyboard::WlKeyboard, ()> for Overlay {
+    fn event(
+        state: &mut Self,
+        _: &wl_keyboard::WlKeyboard,
+        event: wl_keyboard::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            wl_keyboard::Event::Keymap { format, fd, size } => {
+                if format != WEnum::Value(wl_keyboard::KeymapFormat::XkbV1) {
+                    return;
+                }
+                match Xkb::from_fd(fd, size as usize) {
+                    Ok(xkb) => state.xkb = Some(xkb),
+                    Err(e) => eprintln!("[SlickRun] Layer shell: {}", e),
+                }
+            }
+            wl_keyboard::Event::Enter { .. } => {
+                state.focused = true;
+                state.events.push(egui::Event::WindowFocused(true));
+            }
+            wl_keyboard::Event::Leave { .. } => {
+                state.focused = false;
+                state.repeating = None;
+                state.events.push(egui::Event::WindowFocused(false));
+            }
+            wl_keyboard::Event::Key {
+                key, state: key_state, ..
+            } => {
+                // xkb keycodes are evdev codes offset by 8
+                let pressed = key_state == WEnum::Value(wl_keyboard::KeyState::Pressed);
+                state.key(key + 8, pressed, false);
+            }
+            wl_keyboard::Event::Modifiers {
+                mods_depressed,
+                mods_latched,
+                mods_locked,
+                group,
+                ..
+            } => {
+                if let Some(xkb) = &state.xkb {
+                    xkb.update_mask(mods_depressed, mods_latched, mods_locked, group);
+                    state.modifiers = xkb.modifiers();
+                }
+            }
+            wl_keyboard::Event::RepeatInfo { rate, delay } => {
+                state.repeat_rate = rate.max(0) as u32;
+                state.repeat_delay = Duration::from_millis(delay.max(0) as u64);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl PointerHandler for Overlay {
+    fn pointer_frame(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &wl_pointer::WlPointer,
+        events: &[PointerEvent],
+    ) {
+        for event in events {
+            let pos = egui::pos2(event.position.0 as f32, event.position.1 as f32);
+            match event.kind {
+                PointerEventKind::Enter { .. } | PointerEventKind::Motion { .. } => {
+                    self.events.push(egui::Event::PointerMoved(pos));
+                }
+                PointerEventKind::Leave { .. } => {
+                    self.events.push(egui::Event::PointerGone);
+                }
+                PointerEventKind::Press { button, .. } | PointerEventKind::Release { button, .. } => {
+                    let button = match button {
+                        BTN_LEFT => egui::PointerButton::Primary,
+                        BTN_RIGHT => egui::PointerButton::Secondary,
+                        BTN_MIDDLE => egui::PointerButton::Middle,
+                        _ => continue,
+                    };
+                    self.events.push(egui::Event::PointerButton {
+                        pos,
+                        button,
+                        pressed: matches!(event.kind, PointerEventKind::Press { .. }),
+                        modifiers: self.modifiers,
+                    });
+                }
+                PointerEventKind::Axis {
+                    horizontal, vertical, ..
+                } => {
+                    self.events.push(egui::Event::MouseWheel {
+                        unit: egui::MouseWheelUnit::Point,
+                        delta: egui::vec2(-horizontal.absolute as f32, -vertical.absolute as f32),
+                        modifiers: self.modifiers,
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl ProvidesRegistryState for Overlay {
+    fn registry(&mut self) -> &mut RegistryState {
+        &mut self.registry_state
+    }
+
+    registry_handlers![OutputState, SeatState];
+}
+
+delegate_compositor!(Overlay);
+delegate_output!(Overlay);
+delegate_seat!(Overlay);
+delegate_pointer!(Overlay);
+delegate_layer!(Overlay);
+delegate_registry!(Overlay);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keysym_names_map_to_egui_keys() {
+        assert_eq!(egui_key("Return"), Some(egui::Key::Enter));
+        assert_eq!(egui_key("KP_Enter"), Some(egui::Key::Enter));
+        assert_eq!(egui_key("ISO_Left_Tab"), Some(egui::Key::Tab));
+        assert_eq!(egui_key("a"), Some(egui::Key::A));
+        assert_eq!(egui_key("A"), Some(egui::Key::A));
+        assert_eq!(egui_key("Up"), Some(egui::Key::ArrowUp));
+        assert_eq!(egui_key("Shift_L"), None);
+    }
+}
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::commands::MagicWord;
+use crate::settings::Settings;
+use crate::srl;
+
+/// A read-only collection of magic words shared with others, e.g. a checked
+/// out team repository. `path` is a word file or a directory of them.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct WordLibrary {
+    pub path: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A word that came from a library rather than config.json.
+#[derive(Clone)]
+pub struct LibraryWord {
+    pub word: MagicWord,
+    /// File the word was read from, for the Source column.
+    pub source: PathBuf,
+}
+
+/// `~/.config/slickrun/words.d`, listed as a library by default.
+pub fn default_dir() -> PathBuf {
+    let mut p = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    p.push("slickrun");
+    p.push("words.d");
+    p
+}
+
+pub fn default_libraries() -> Vec<WordLibrary> {
+    vec![WordLibrary {
+        path: default_dir().to_string_lossy().to_string(),
+        enabled: true,
+    }]
+}
+
+pub fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+fn is_word_file(path: &Path) -> bool {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    matches!(extension.as_str(), "json" | "toml" | "srl" | "qrs")
+}
+
+/// The word files a library consists of, in name order.
+fn files_of(library: &WordLibrary) -> Result<Vec<PathBuf>, String> {
+    let path = expand_home(&library.path);
+    if path.is_file() {
+        return Ok(vec![path]);
+    }
+    if !path.is_dir() {
+        // The default words.d only exists once someone puts something there
+        if path == default_dir() {
+            return Ok(Vec::new());
+        }
+        return Err(format!("{} does not exist", path.display()));
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&path)
+        .map_err(|e| format!("{}: {}", path.display(), e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && is_word_file(p))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn read_file(path: &Path) -> Result<Vec<MagicWord>, String> {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if extension == "srl" || extension == "qrs" {
+        return Ok(srl::import_file(path)?.words);
+    }
+    Settings::import_magic_words(path)
+}
+
+/// Read every enabled library. When several define the same keyword the one
+/// listed first wins; problems are returned as messages rather than failing
+/// the whole load, so one bad file doesn't hide the others.
+pub fn load(libraries: &[WordLibrary]) -> (Vec<LibraryWord>, Vec<String>) {
+    let mut words: Vec<LibraryWord> = Vec::new();
+    let mut errors = Vec::new();
+    for library in libraries.iter().filter(|l| l.enabled) {
+        let files = match files_of(library) {
+            Ok(files) => files,
+            Err(e) => {
+                errors.push(e);
+                continue;
+            }
+        };
+        for file in files {
+            match read_file(&file) {
+                Ok(file_words) => {
+                    for word in file_words {
+                        if !words.iter().any(|w| w.word.keyword.eq_ignore_ascii_case(&word.keyword)) {
+                            words.push(LibraryWord { word, source: file.clone() });
+                        }
+                    }
+                }
+                Err(e) => errors.push(format!("{}: {}", file.display(), e)),
+            }
+        }
+    }
+    (words, errors)
+}
@@ -1,20 +1,49 @@
 mod app;
 mod commands;
+mod config_format;
+mod conflicts;
+mod desktop;
+mod import;
+mod keys;
+mod kwin;
+mod layer_shell;
+mod libraries;
+mod migrations;
+mod modifier_tap;
+mod monitors;
 mod settings;
+mod srl;
+mod validation;
+mod window_backend;
+mod wlroots;
 
 use eframe::egui;
-use tray_icon::menu::{Menu, MenuId, MenuItem};
+use tray_icon::menu::{CheckMenuItem, Menu, MenuId, MenuItem, Submenu};
 use tray_icon::{Icon, TrayIconBuilder};
 
 fn main() -> eframe::Result<()> {
-    // Force X11 backend — global-hotkey uses XGrabKey which needs X11
-    unsafe {
-        std::env::set_var("WINIT_UNIX_BACKEND", "x11");
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("--check-config") {
+        std::process::exit(validation::check_config_command(args.get(2).map(String::as_str)));
+    }
+    if args.get(1).map(String::as_str) == Some("--convert-config") {
+        std::process::exit(config_format::convert_command(&args[2..]));
+    }
+
+    let desktop = desktop::Desktop::detect();
+
+    // Force X11 backend — global-hotkey uses XGrabKey which needs X11.
+    // wlroots compositors get a layer-shell overlay instead of a winit window.
+    if !desktop.is_wlroots() {
+        unsafe {
+            std::env::set_var("WINIT_UNIX_BACKEND", "x11");
+        }
     }
 
     gtk::init().expect("Failed to init GTK");
 
-    let tray = build_tray_icon();
+    let settings = settings::Settings::load();
+    let tray = build_tray_icon(&settings);
 
     // Pump GTK events so libappindicator registers the icon via D-Bus
     for _ in 0..50 {
@@ -24,21 +53,25 @@ fn main() -> eframe::Result<()> {
         std::thread::sleep(std::time::Duration::from_millis(10));
     }
 
-    let settings = settings::Settings::load();
+    if desktop.is_wlroots() {
+        if let Err(e) = layer_shell::run(tray, desktop) {
+            eprintln!("[SlickRun] Layer shell: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let size = settings.appearance().size();
 
     let mut viewport = egui::ViewportBuilder::default()
         .with_decorations(false)
-        .with_inner_size([settings.window_width, settings.window_height])
+        .with_inner_size([size.0, size.1])
         .with_always_on_top()
         .with_transparent(true)
         .with_resizable(false);
 
-    if let (Some(x), Some(y)) = (settings.window_x, settings.window_y) {
+    if let Some((x, y)) = monitors::place_window(&settings, size) {
         viewport = viewport.with_position([x, y]);
-    } else if let Some((sx, sy)) = screen_size() {
-        let cx = (sx as f32 - settings.window_width) / 2.0;
-        let cy = (sy as f32 - settings.window_height) / 2.0;
-        viewport = viewport.with_position([cx, cy]);
     }
 
     let native_options = eframe::NativeOptions {
@@ -49,30 +82,45 @@ fn main() -> eframe::Result<()> {
     eframe::run_native(
         "SlickRun",
         native_options,
-        Box::new(move |cc| Ok(Box::new(app::LauncherApp::new(cc, tray)))),
+        Box::new(move |cc| Ok(Box::new(app::LauncherApp::new(&cc.egui_ctx, tray, desktop)))),
     )
 }
 
-fn screen_size() -> Option<(u16, u16)> {
-    use x11rb::connection::Connection;
-    let (conn, screen_num) = x11rb::connect(None).ok()?;
-    let screen = &conn.setup().roots[screen_num];
-    Some((screen.width_in_pixels, screen.height_in_pixels))
-}
-
-fn build_tray_icon() -> tray_icon::TrayIcon {
+/// The tray menu. Profiles get a submenu with the active one checked; its
+/// items have ids "profile:<name>", and "profile:" for none.
+pub fn tray_menu(settings: &settings::Settings) -> Menu {
     let menu = Menu::new();
     let show_hide = MenuItem::with_id(MenuId::new("show_hide"), "Show/Hide", true, None);
     let settings_item = MenuItem::with_id(MenuId::new("settings"), "Settings", true, None);
     let quit = MenuItem::with_id(MenuId::new("quit"), "Quit", true, None);
     menu.append(&show_hide).unwrap();
     menu.append(&settings_item).unwrap();
+    if !settings.profiles.is_empty() {
+        let profiles = Submenu::new("Profile", true);
+        let active = settings.active_profile().map(|p| p.name.as_str());
+        let none = CheckMenuItem::with_id(MenuId::new("profile:"), "None", true, active.is_none(), None);
+        profiles.append(&none).unwrap();
+        for profile in &settings.profiles {
+            let item = CheckMenuItem::with_id(
+                MenuId::new(format!("profile:{}", profile.name)),
+                &profile.name,
+                true,
+                active == Some(profile.name.as_str()),
+                None,
+            );
+            profiles.append(&item).unwrap();
+        }
+        menu.append(&profiles).unwrap();
+    }
     menu.append(&quit).unwrap();
+    menu
+}
 
+fn build_tray_icon(settings: &settings::Settings) -> tray_icon::TrayIcon {
     let icon = create_icon();
 
     TrayIconBuilder::new()
-        .with_menu(Box::new(menu))
+        .with_menu(Box::new(tray_menu(settings)))
         .with_tooltip("SlickRun")
         .with_icon(icon)
         .build()
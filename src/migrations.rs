@@ -0,0 +1,130 @@
+use serde_json::Value;
+
+use crate::keys;
+
+/// Schema version written by this build. Bump it and append a step to
+/// `MIGRATIONS` whenever a change to `Settings`, `MagicWord` or `HotkeyConfig`
+/// would otherwise drop or misread data in existing config files.
+pub const CURRENT_VERSION: u32 = 1;
+
+/// `MIGRATIONS[n]` upgrades a version-n config to version n+1.
+const MIGRATIONS: &[fn(&mut Value)] = &[v0_normalize_key_names];
+
+const _: () = assert!(MIGRATIONS.len() == CURRENT_VERSION as usize);
+
+/// Upgrade a parsed config.json in place, one version at a time.
+/// Files without `schema_version` predate versioning and count as version 0.
+/// Files from a newer build are rejected rather than loaded with fields missing.
+pub fn migrate(config: &mut Value) -> Result<(), String> {
+    let version = config
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as usize;
+    if version > CURRENT_VERSION as usize {
+        return Err(format!(
+            "it was written by a newer SlickRun (schema version {}, this build understands up to {})",
+            version, CURRENT_VERSION
+        ));
+    }
+    for (from, step) in MIGRATIONS.iter().enumerate().skip(version) {
+        step(config);
+        eprintln!(
+            "[SlickRun] Migrated config from schema version {} to {}",
+            from,
+            from + 1
+        );
+    }
+    if let Some(obj) = config.as_object_mut() {
+        obj.insert("schema_version".into(), CURRENT_VERSION.into());
+    }
+    Ok(())
+}
+
+/// v0 → v1: case-normalize key names against `keys::KEYS`, for the toggle
+/// hotkey and every word hotkey. Key names have always been matched exactly,
+/// so hand-edited spellings like "q" or "space" never registered; this cleans
+/// them up once instead of silently ignoring them.
+fn v0_normalize_key_names(config: &mut Value) {
+    fn normalize(hotkey: &mut Value) {
+        let Some(key) = hotkey.get_mut("key") else {
+            return;
+        };
+        let known = key.as_str().and_then(|name| {
+            keys::KEYS
+                .iter()
+                .find(|k| k.name.eq_ignore_ascii_case(name))
+        });
+        if let Some(info) = known {
+            *key = info.name.into();
+        }
+    }
+
+    if let Some(hotkey) = config.get_mut("hotkey") {
+        normalize(hotkey);
+    }
+    if let Some(words) = config.get_mut("magic_words").and_then(Value::as_array_mut) {
+        for word in words {
+            if let Some(hotkey) = word.get_mut("hotkey") {
+                normalize(hotkey);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn v0_normalizes_old_key_spellings() {
+        for (old, new) in [
+            ("q", "Q"),
+            ("space", "Space"),
+            ("SPACE", "Space"),
+            ("f5", "F5"),
+            ("escape", "Escape"),
+            ("7", "7"),
+        ] {
+            let mut config = json!({
+                "hotkey": { "key": old },
+                "magic_words": [{ "keyword": "g", "hotkey": { "key": old } }],
+            });
+            v0_normalize_key_names(&mut config);
+            assert_eq!(config["hotkey"]["key"], new, "toggle hotkey {:?}", old);
+            assert_eq!(
+                config["magic_words"][0]["hotkey"]["key"], new,
+                "word hotkey {:?}",
+                old
+            );
+        }
+    }
+
+    #[test]
+    fn v0_leaves_unknown_keys_and_missing_hotkeys_alone() {
+        let mut config = json!({
+            "hotkey": { "key": "NoSuchKey" },
+            "magic_words": [{ "keyword": "g" }],
+        });
+        v0_normalize_key_names(&mut config);
+        assert_eq!(config["hotkey"]["key"], "NoSuchKey");
+        assert!(config["magic_words"][0].get("hotkey").is_none());
+    }
+
+    #[test]
+    fn migrate_stamps_and_normalizes_unversioned_file() {
+        let mut config = json!({ "hotkey": { "key": "q" }, "magic_words": [] });
+        migrate(&mut config).unwrap();
+        assert_eq!(config["schema_version"], CURRENT_VERSION);
+        assert_eq!(config["hotkey"]["key"], "Q");
+    }
+
+    #[test]
+    fn migrate_rejects_newer_schema() {
+        let mut config = json!({ "schema_version": CURRENT_VERSION + 1, "hotkey": { "key": "q" } });
+        assert!(migrate(&mut config).is_err());
+        // Left untouched, so a newer build can still read it
+        assert_eq!(config["hotkey"]["key"], "q");
+        assert_eq!(config["schema_version"], CURRENT_VERSION + 1);
+    }
+}
@@ -0,0 +1,310 @@
+use eframe::egui;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::settings::ModifierTap;
+
+/// Longest press that still counts as a tap.
+const TAP_MAX: Duration = Duration::from_millis(250);
+/// Longest gap between the two taps of a double tap.
+const DOUBLE_TAP_GAP: Duration = Duration::from_millis(400);
+
+/// Modifier indices in the X11 modifier mapping.
+const SHIFT_INDEX: usize = 0;
+const CONTROL_INDEX: usize = 2;
+const MOD1_INDEX: usize = 3; // Alt
+const MOD4_INDEX: usize = 6; // Super
+
+/// Watches raw key events through the X RECORD extension and raises the toggle
+/// signal on a modifier tap. Passive grabs can't see a modifier pressed on its
+/// own, so this is the only way to get Ctrl-Ctrl on X11. The thread starts the
+/// first time a tap mode is enabled and afterwards just follows `trigger`.
+pub struct TapMonitor {
+    trigger: Arc<AtomicU8>,
+    started: bool,
+    signal: Arc<AtomicBool>,
+    ctx: egui::Context,
+}
+
+impl TapMonitor {
+    pub fn new(ctx: egui::Context, signal: Arc<AtomicBool>) -> Self {
+        Self {
+            trigger: Arc::new(AtomicU8::new(0)),
+            started: false,
+            signal,
+            ctx,
+        }
+    }
+
+    pub fn set(&mut self, tap: ModifierTap) {
+        let index = ModifierTap::ALL.iter().position(|t| *t == tap).unwrap_or(0);
+        self.trigger.store(index as u8, Ordering::SeqCst);
+        if tap == ModifierTap::Off || self.started || std::env::var_os("DISPLAY").is_none() {
+            return;
+        }
+        if std::env::var("XDG_SESSION_TYPE").as_deref() == Ok("wayland") {
+            eprintln!("[SlickRun] Modifier taps only see keys typed into X11 apps on a Wayland session");
+        }
+        self.started = true;
+
+        let trigger = self.trigger.clone();
+        let signal = self.signal.clone();
+        let ctx = self.ctx.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = run(&trigger, &signal, &ctx) {
+                eprintln!("[SlickRun] Modifier tap monitor stopped: {e}");
+            }
+        });
+    }
+}
+
+/// Tap detection state for one recording session.
+#[derive(Default)]
+struct TapState {
+    /// When the watched modifier went down, if nothing else was pressed since.
+    pressed_at: Option<Instant>,
+    /// When the previous clean tap ended, waiting for a second one.
+    last_tap: Option<Instant>,
+}
+
+impl TapState {
+    /// Feed one key/button event that arrived at `now`; returns true when the
+    /// launcher should toggle.
+    fn event(&mut self, tap: ModifierTap, is_watched: bool, pressed: bool, now: Instant) -> bool {
+        if !is_watched {
+            // Any other key or a click (Ctrl+C, Shift+click) cancels the tap
+            if pressed {
+                self.pressed_at = None;
+                self.last_tap = None;
+            }
+            return false;
+        }
+        if pressed {
+            // Auto-repeat sends more presses while held — keep the first
+            self.pressed_at.get_or_insert(now);
+            return false;
+        }
+
+        let Some(down) = self.pressed_at.take() else {
+            return false;
+        };
+        if now - down > TAP_MAX {
+            self.last_tap = None;
+            return false;
+        }
+        if tap == ModifierTap::SuperAlone {
+            return true;
+        }
+        match self.last_tap.take() {
+            Some(prev) if now - prev <= DOUBLE_TAP_GAP => true,
+            _ => {
+                self.last_tap = Some(now);
+                false
+            }
+        }
+    }
+}
+
+fn run(
+    trigger: &AtomicU8,
+    signal: &AtomicBool,
+    ctx: &egui::Context,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::record::{self, ConnectionExt as _};
+    use x11rb::protocol::xproto::{self, ConnectionExt as _};
+
+    // RECORD wants one connection for control and a second that blocks on the data stream
+    let (ctrl, _) = x11rb::connect(None)?;
+    let (data, _) = x11rb::connect(None)?;
+    ctrl.record_query_version(1, 13)?.reply()?;
+
+    let mapping = ctrl.get_modifier_mapping()?.reply()?;
+    let per_modifier = mapping.keycodes_per_modifier() as usize;
+    let keycodes_of = |index: usize| -> Vec<u8> {
+        mapping.keycodes[index * per_modifier..(index + 1) * per_modifier]
+            .iter()
+            .copied()
+            .filter(|&k| k != 0)
+            .collect()
+    };
+    let watched = |tap: ModifierTap| -> Vec<u8> {
+        match tap {
+            ModifierTap::Off => Vec::new(),
+            ModifierTap::DoubleCtrl => keycodes_of(CONTROL_INDEX),
+            ModifierTap::DoubleShift => keycodes_of(SHIFT_INDEX),
+            ModifierTap::DoubleAlt => keycodes_of(MOD1_INDEX),
+            ModifierTap::DoubleSuper | ModifierTap::SuperAlone => keycodes_of(MOD4_INDEX),
+        }
+    };
+
+    let context = ctrl.generate_id()?;
+    let empty = record::Range8 { first: 0, last: 0 };
+    let empty_ext = record::ExtRange {
+        major: empty,
+        minor: record::Range16 { first: 0, last: 0 },
+    };
+    let range = record::Range {
+        core_requests: empty,
+        core_replies: empty,
+        ext_requests: empty_ext,
+        ext_replies: empty_ext,
+        delivered_events: empty,
+        device_events: record::Range8 {
+            first: xproto::KEY_PRESS_EVENT,
+            last: xproto::BUTTON_PRESS_EVENT,
+        },
+        errors: empty,
+        client_started: false,
+        client_died: false,
+    };
+    ctrl.record_create_context(context, 0, &[record::CS::ALL_CLIENTS.into()], &[range])?
+        .check()?;
+    eprintln!("[SlickRun] Modifier tap monitor running");
+
+    // Category of replies carrying intercepted server data
+    const FROM_SERVER: u8 = 0;
+    let mut state = TapState::default();
+    for reply in data.record_enable_context(context)? {
+        let reply = reply?;
+        if reply.category != FROM_SERVER || reply.client_swapped {
+            continue;
+        }
+        let tap = ModifierTap::ALL
+            .get(trigger.load(Ordering::SeqCst) as usize)
+            .copied()
+            .unwrap_or_default();
+        if tap == ModifierTap::Off {
+            state = TapState::default();
+            continue;
+        }
+        let keys = watched(tap);
+        let now = Instant::now();
+        // Core device events are fixed 32-byte records: type, detail (keycode), ...
+        for event in reply.data.chunks_exact(32) {
+            let (kind, detail) = (event[0] & 0x7f, event[1]);
+            let is_key = kind == xproto::KEY_PRESS_EVENT || kind == xproto::KEY_RELEASE_EVENT;
+            let pressed = kind != xproto::KEY_RELEASE_EVENT;
+            if state.event(tap, is_key && keys.contains(&detail), pressed, now) {
+                eprintln!("[SlickRun] {} detected", tap.label());
+                signal.store(true, Ordering::SeqCst);
+                ctx.request_repaint();
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    /// Replays `(ms, is_watched, pressed)` events and returns the times at
+    /// which the launcher toggled.
+    fn replay(tap: ModifierTap, events: &[(u64, bool, bool)]) -> Vec<u64> {
+        let start = Instant::now();
+        let mut state = TapState::default();
+        events
+            .iter()
+            .filter(|&&(ms, is_watched, pressed)| {
+                state.event(tap, is_watched, pressed, start + MS * ms as u32)
+            })
+            .map(|&(ms, ..)| ms)
+            .collect()
+    }
+
+    #[test]
+    fn double_tap_within_the_gap_toggles() {
+        let taps = [
+            (0, true, true),
+            (100, true, false),
+            (300, true, true),
+            (400, true, false),
+        ];
+        assert_eq!(replay(ModifierTap::DoubleCtrl, &taps), [400]);
+        // Auto-repeat presses while held don't restart the tap
+        let repeat = [
+            (0, true, true),
+            (50, true, true),
+            (100, true, false),
+            (200, true, true),
+            (300, true, false),
+        ];
+        assert_eq!(replay(ModifierTap::DoubleCtrl, &repeat), [300]);
+    }
+
+    #[test]
+    fn second_tap_after_the_gap_starts_over() {
+        let taps = [
+            (0, true, true),
+            (100, true, false),
+            (600, true, true),
+            (700, true, false),
+            (800, true, true),
+            (900, true, false),
+        ];
+        assert_eq!(replay(ModifierTap::DoubleShift, &taps), [900]);
+    }
+
+    #[test]
+    fn holding_longer_than_a_tap_does_not_count() {
+        let held = [
+            (0, true, true),
+            (100, true, false),
+            (200, true, true),
+            (500, true, false),
+        ];
+        assert_eq!(replay(ModifierTap::DoubleAlt, &held), Vec::<u64>::new());
+        let exactly_max = [(0, true, true), (250, true, false)];
+        assert_eq!(replay(ModifierTap::SuperAlone, &exactly_max), [250]);
+        let too_long = [(0, true, true), (251, true, false)];
+        assert_eq!(
+            replay(ModifierTap::SuperAlone, &too_long),
+            Vec::<u64>::new()
+        );
+    }
+
+    #[test]
+    fn another_key_or_click_cancels_the_tap() {
+        // Ctrl+C: the C press cancels the held Ctrl
+        let chord = [
+            (0, true, true),
+            (50, false, true),
+            (80, false, false),
+            (100, true, false),
+        ];
+        assert_eq!(replay(ModifierTap::SuperAlone, &chord), Vec::<u64>::new());
+        // A click between the two taps forgets the first one
+        let clicked = [
+            (0, true, true),
+            (100, true, false),
+            (150, false, true),
+            (200, true, true),
+            (300, true, false),
+        ];
+        assert_eq!(replay(ModifierTap::DoubleCtrl, &clicked), Vec::<u64>::new());
+        // Releasing another key doesn't cancel anything
+        let released = [
+            (0, true, true),
+            (100, true, false),
+            (150, false, false),
+            (200, true, true),
+            (300, true, false),
+        ];
+        assert_eq!(replay(ModifierTap::DoubleCtrl, &released), [300]);
+    }
+
+    #[test]
+    fn super_alone_toggles_on_a_single_tap() {
+        let taps = [
+            (0, true, true),
+            (100, true, false),
+            (200, true, true),
+            (300, true, false),
+        ];
+        assert_eq!(replay(ModifierTap::SuperAlone, &taps), [100, 300]);
+    }
+}
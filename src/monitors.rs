@@ -0,0 +1,377 @@
+use crate::settings::{MonitorPolicy, PlacementPreset, Settings};
+
+/// A monitor as reported by RandR, in root-window coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Monitor {
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub primary: bool,
+}
+
+impl Monitor {
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    /// Top-left position that centers a window of `size` on this monitor.
+    pub fn center(&self, size: (f32, f32)) -> (f32, f32) {
+        (
+            self.x as f32 + (self.width as f32 - size.0) / 2.0,
+            self.y as f32 + (self.height as f32 - size.1) / 2.0,
+        )
+    }
+
+    /// Clamp a top-left position so a window of `size` stays fully on this monitor.
+    fn clamp(&self, pos: (f32, f32), size: (f32, f32)) -> (f32, f32) {
+        let max_x = (self.x + self.width) as f32 - size.0;
+        let max_y = (self.y + self.height) as f32 - size.1;
+        (
+            pos.0.min(max_x).max(self.x as f32),
+            pos.1.min(max_y).max(self.y as f32),
+        )
+    }
+}
+
+/// Active monitors via RandR 1.5, falling back to the root window as a single monitor.
+pub fn list() -> Vec<Monitor> {
+    match list_inner() {
+        Ok(monitors) => monitors,
+        Err(e) => {
+            eprintln!("[SlickRun] RandR monitor query failed: {e}");
+            Vec::new()
+        }
+    }
+}
+
+fn list_inner() -> Result<Vec<Monitor>, Box<dyn std::error::Error>> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::randr::ConnectionExt as _;
+    use x11rb::protocol::xproto::ConnectionExt as _;
+
+    let (conn, screen_num) = x11rb::connect(None)?;
+    let screen = &conn.setup().roots[screen_num];
+
+    // Without RandR 1.5 the request can't even be sent, so both failures fall back
+    let reply = conn
+        .randr_get_monitors(screen.root, true)
+        .map_err(x11rb::errors::ReplyError::from)
+        .and_then(|cookie| cookie.reply());
+    let monitors = match reply {
+        Ok(reply) => reply.monitors,
+        Err(_) => {
+            // No RandR 1.5 — treat the whole root window as one monitor
+            return Ok(vec![Monitor {
+                name: "default".into(),
+                x: 0,
+                y: 0,
+                width: screen.width_in_pixels as i32,
+                height: screen.height_in_pixels as i32,
+                primary: true,
+            }]);
+        }
+    };
+
+    let mut result = Vec::new();
+    for m in monitors {
+        let name = conn
+            .get_atom_name(m.name)?
+            .reply()
+            .map(|r| String::from_utf8_lossy(&r.name).to_string())
+            .unwrap_or_default();
+        result.push(Monitor {
+            name,
+            x: m.x as i32,
+            y: m.y as i32,
+            width: m.width as i32,
+            height: m.height as i32,
+            primary: m.primary,
+        });
+    }
+    Ok(result)
+}
+
+/// Identifies the current arrangement, e.g. "DP-1:2560x1440+0+0,HDMI-1:1920x1080+2560+0".
+/// Saved positions are keyed by this so docking/undocking doesn't strand the window.
+pub fn layout_key(monitors: &[Monitor]) -> String {
+    let mut parts: Vec<String> = monitors
+        .iter()
+        .map(|m| format!("{}:{}x{}+{}+{}", m.name, m.width, m.height, m.x, m.y))
+        .collect();
+    parts.sort();
+    parts.join(",")
+}
+
+fn pointer_position() -> Option<(i32, i32)> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::ConnectionExt;
+
+    let (conn, screen_num) = x11rb::connect(None).ok()?;
+    let root = conn.setup().roots[screen_num].root;
+    let reply = conn.query_pointer(root).ok()?.reply().ok()?;
+    Some((reply.root_x as i32, reply.root_y as i32))
+}
+
+/// Center of the window named by _NET_ACTIVE_WINDOW.
+fn focused_window_center() -> Option<(i32, i32)> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::{AtomEnum, ConnectionExt};
+
+    let (conn, screen_num) = x11rb::connect(None).ok()?;
+    let root = conn.setup().roots[screen_num].root;
+    let net_active = conn
+        .intern_atom(false, b"_NET_ACTIVE_WINDOW")
+        .ok()?
+        .reply()
+        .ok()?
+        .atom;
+    let prop = conn
+        .get_property(false, root, net_active, AtomEnum::WINDOW, 0, 1)
+        .ok()?
+        .reply()
+        .ok()?;
+    let window = prop.value32()?.next()?;
+    if window == 0 {
+        return None;
+    }
+    let geom = conn.get_geometry(window).ok()?.reply().ok()?;
+    let origin = conn
+        .translate_coordinates(window, root, 0, 0)
+        .ok()?
+        .reply()
+        .ok()?;
+    Some((
+        origin.dst_x as i32 + geom.width as i32 / 2,
+        origin.dst_y as i32 + geom.height as i32 / 2,
+    ))
+}
+
+fn primary(monitors: &[Monitor]) -> Option<&Monitor> {
+    monitors.iter().find(|m| m.primary).or(monitors.first())
+}
+
+fn monitor_at(monitors: &[Monitor], (x, y): (i32, i32)) -> Option<&Monitor> {
+    monitors.iter().find(|m| m.contains(x, y))
+}
+
+/// The monitor the launcher should appear on under `policy`.
+/// Falls back to the primary monitor when the pointer/focus can't be resolved
+/// (e.g. the pointer is over a native Wayland window XWayland can't see).
+pub fn target_monitor(policy: MonitorPolicy, monitors: &[Monitor]) -> Option<&Monitor> {
+    let found = match policy {
+        MonitorPolicy::Pointer => pointer_position().and_then(|p| monitor_at(monitors, p)),
+        MonitorPolicy::FocusedWindow => focused_window_center().and_then(|p| monitor_at(monitors, p)),
+        MonitorPolicy::Primary | MonitorPolicy::Fixed => None,
+    };
+    found.or_else(|| primary(monitors))
+}
+
+/// Remembered top-left position for this layout, or the legacy window_x/window_y
+/// if it is still on one of the connected monitors.
+fn saved_position(settings: &Settings, monitors: &[Monitor], layout: &str) -> Option<(f32, f32)> {
+    if let Some([x, y]) = settings.saved_positions.get(layout) {
+        return Some((*x, *y));
+    }
+    let (x, y) = (settings.window_x?, settings.window_y?);
+    monitor_at(monitors, (x as i32, y as i32)).map(|_| (x, y))
+}
+
+/// Where to put a launcher of `size` when it is shown.
+/// Returns None when no monitor information is available at all.
+pub fn place_window(settings: &Settings, size: (f32, f32)) -> Option<(f32, f32)> {
+    let monitors = list();
+    if monitors.is_empty() {
+        return settings.window_x.zip(settings.window_y);
+    }
+    place_on(settings, &monitors, size)
+}
+
+/// `place_window` for a known set of monitors.
+fn place_on(settings: &Settings, monitors: &[Monitor], size: (f32, f32)) -> Option<(f32, f32)> {
+    let layout = layout_key(monitors);
+    let saved = saved_position(settings, monitors, &layout);
+
+    if settings.placement_preset != PlacementPreset::Remembered {
+        return place_preset(settings, monitors, saved, size);
+    }
+
+    if settings.monitor_policy == MonitorPolicy::Fixed {
+        return saved.or_else(|| primary(monitors).map(|m| m.center(size)));
+    }
+
+    let target = target_monitor(settings.monitor_policy, monitors)?;
+    // Keep the remembered spot relative to its monitor, transplanted onto the target
+    let saved_on = saved.and_then(|pos| {
+        monitor_at(monitors, (pos.0 as i32, pos.1 as i32)).map(|m| (pos, m))
+    });
+    Some(match saved_on {
+        Some((pos, from)) => target.clamp(
+            (
+                target.x as f32 + pos.0 - from.x as f32,
+                target.y as f32 + pos.1 - from.y as f32,
+            ),
+            size,
+        ),
+        None => target.center(size),
+    })
+}
+
+/// Position for a named preset, relative to the monitor the policy picks
+/// (for Fixed, the monitor the window was last left on).
+fn place_preset(
+    settings: &Settings,
+    monitors: &[Monitor],
+    saved: Option<(f32, f32)>,
+    size: (f32, f32),
+) -> Option<(f32, f32)> {
+    if settings.placement_preset == PlacementPreset::NearCursor {
+        let (px, py) = pointer_position()?;
+        let monitor = monitor_at(monitors, (px, py)).or_else(|| primary(monitors))?;
+        // Slightly below the pointer so the cursor doesn't cover the input
+        return Some(monitor.clamp((px as f32 - size.0 / 2.0, py as f32 + 16.0), size));
+    }
+
+    let monitor = match settings.monitor_policy {
+        MonitorPolicy::Fixed => saved
+            .and_then(|(x, y)| monitor_at(monitors, (x as i32, y as i32)))
+            .or_else(|| primary(monitors)),
+        policy => target_monitor(policy, monitors),
+    }?;
+    let (cx, cy) = monitor.center(size);
+    let margin = settings.placement_margin;
+    let pos = match settings.placement_preset {
+        PlacementPreset::TopCenter => (cx, monitor.y as f32 + margin),
+        PlacementPreset::BottomCenter => (cx, (monitor.y + monitor.height) as f32 - size.1 - margin),
+        _ => (cx, cy),
+    };
+    Some(monitor.clamp(pos, size))
+}
+
+/// Center a window of `size` on the monitor chosen by the placement policy.
+pub fn center_window(settings: &Settings, size: (f32, f32)) -> Option<(f32, f32)> {
+    let monitors = list();
+    target_monitor(settings.monitor_policy, &monitors).map(|m| m.center(size))
+}
+
+/// Layout key for the currently connected monitors, if RandR is reachable.
+pub fn current_layout_key() -> Option<String> {
+    let monitors = list();
+    if monitors.is_empty() {
+        None
+    } else {
+        Some(layout_key(&monitors))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(name: &str, x: i32, y: i32, width: i32, height: i32, primary: bool) -> Monitor {
+        Monitor {
+            name: name.into(),
+            x,
+            y,
+            width,
+            height,
+            primary,
+        }
+    }
+
+    /// A 2560x1440 primary on the left and a 1920x1080 to its right.
+    fn dual() -> Vec<Monitor> {
+        vec![
+            monitor("DP-1", 0, 0, 2560, 1440, true),
+            monitor("HDMI-1", 2560, 0, 1920, 1080, false),
+        ]
+    }
+
+    fn settings(preset: PlacementPreset, policy: MonitorPolicy) -> Settings {
+        Settings {
+            placement_preset: preset,
+            monitor_policy: policy,
+            placement_margin: 40.0,
+            ..Settings::default()
+        }
+    }
+
+    #[test]
+    fn contains_is_half_open() {
+        let m = monitor("DP-1", 100, 50, 200, 100, true);
+        assert!(m.contains(100, 50));
+        assert!(m.contains(299, 149));
+        assert!(!m.contains(300, 50));
+        assert!(!m.contains(100, 150));
+        assert!(!m.contains(99, 50));
+    }
+
+    #[test]
+    fn center_and_clamp_stay_on_the_monitor() {
+        let m = monitor("HDMI-1", 2560, 0, 1920, 1080, false);
+        assert_eq!(m.center((400.0, 80.0)), (3320.0, 500.0));
+        assert_eq!(m.clamp((4400.0, -20.0), (400.0, 80.0)), (4080.0, 0.0));
+        assert_eq!(m.clamp((100.0, 1070.0), (400.0, 80.0)), (2560.0, 1000.0));
+        assert_eq!(m.clamp((3000.0, 300.0), (400.0, 80.0)), (3000.0, 300.0));
+    }
+
+    #[test]
+    fn layout_key_ignores_monitor_order() {
+        let mut monitors = dual();
+        let key = layout_key(&monitors);
+        assert_eq!(key, "DP-1:2560x1440+0+0,HDMI-1:1920x1080+2560+0");
+        monitors.reverse();
+        assert_eq!(layout_key(&monitors), key);
+    }
+
+    #[test]
+    fn presets_use_the_margin_on_the_primary_monitor() {
+        let size = (400.0, 80.0);
+        let at = |preset| place_on(&settings(preset, MonitorPolicy::Primary), &dual(), size);
+        assert_eq!(at(PlacementPreset::TopCenter), Some((1080.0, 40.0)));
+        assert_eq!(at(PlacementPreset::Center), Some((1080.0, 680.0)));
+        assert_eq!(at(PlacementPreset::BottomCenter), Some((1080.0, 1320.0)));
+    }
+
+    #[test]
+    fn fixed_preset_follows_the_monitor_it_was_left_on() {
+        let mut s = settings(PlacementPreset::TopCenter, MonitorPolicy::Fixed);
+        s.window_x = Some(3000.0);
+        s.window_y = Some(500.0);
+        assert_eq!(place_on(&s, &dual(), (400.0, 80.0)), Some((3320.0, 40.0)));
+    }
+
+    #[test]
+    fn remembered_position_is_per_layout() {
+        let mut s = settings(PlacementPreset::Remembered, MonitorPolicy::Fixed);
+        assert_eq!(place_on(&s, &dual(), (400.0, 80.0)), Some((1080.0, 680.0)));
+        s.saved_positions
+            .insert(layout_key(&dual()), [2700.0, 100.0]);
+        assert_eq!(place_on(&s, &dual(), (400.0, 80.0)), Some((2700.0, 100.0)));
+
+        // Legacy position off every connected monitor is ignored
+        let mut legacy = settings(PlacementPreset::Remembered, MonitorPolicy::Fixed);
+        legacy.window_x = Some(9000.0);
+        legacy.window_y = Some(100.0);
+        assert_eq!(
+            place_on(&legacy, &dual(), (400.0, 80.0)),
+            Some((1080.0, 680.0))
+        );
+    }
+
+    #[test]
+    fn remembered_position_moves_to_the_primary_monitor() {
+        let mut s = settings(PlacementPreset::Remembered, MonitorPolicy::Primary);
+        // Left 100,60 into HDMI-1; shown on DP-1 at the same offset
+        s.saved_positions
+            .insert(layout_key(&dual()), [2660.0, 60.0]);
+        assert_eq!(place_on(&s, &dual(), (400.0, 80.0)), Some((100.0, 60.0)));
+        // Clamped when the offset doesn't fit the smaller target
+        let mut monitors = dual();
+        monitors[0].primary = false;
+        monitors[1].primary = true;
+        s.saved_positions.insert(layout_key(&monitors), [2300.0, 1400.0]);
+        assert_eq!(place_on(&s, &monitors, (400.0, 80.0)), Some((4080.0, 1000.0)));
+    }
+}
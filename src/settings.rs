@@ -1,11 +1,21 @@
 use eframe::egui;
-use global_hotkey::hotkey::{Code, HotKey, Modifiers};
+use egui_extras::{Column, TableBuilder};
+use global_hotkey::hotkey::{HotKey, Modifiers};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
 
-use crate::commands::{MagicWord, StartMode};
-
-#[derive(Serialize, Deserialize, Clone, Debug)]
+use crate::commands::{self, MagicWord, StartMode};
+use crate::config_format::ConfigFormat;
+use crate::conflicts;
+use crate::desktop::Desktop;
+use crate::keys;
+use crate::libraries::{self, LibraryWord, WordLibrary};
+use crate::migrations;
+use crate::import::{self, PendingImport, Resolution};
+use crate::validation;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct HotkeyConfig {
     pub super_key: bool,
     pub ctrl: bool,
@@ -28,7 +38,7 @@ impl Default for HotkeyConfig {
 
 impl HotkeyConfig {
     pub fn to_hotkey(&self) -> Option<HotKey> {
-        let code = key_name_to_code(&self.key)?;
+        let code = keys::lookup(&self.key)?.code;
         let mut mods = Modifiers::empty();
         if self.super_key {
             mods |= Modifiers::SUPER;
@@ -65,59 +75,148 @@ impl HotkeyConfig {
     }
 }
 
-pub const AVAILABLE_KEYS: &[&str] = &[
-    "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R",
-    "S", "T", "U", "V", "W", "X", "Y", "Z", "F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8",
-    "F9", "F10", "F11", "F12", "Space",
-];
-
-fn key_name_to_code(name: &str) -> Option<Code> {
-    match name {
-        "A" => Some(Code::KeyA),
-        "B" => Some(Code::KeyB),
-        "C" => Some(Code::KeyC),
-        "D" => Some(Code::KeyD),
-        "E" => Some(Code::KeyE),
-        "F" => Some(Code::KeyF),
-        "G" => Some(Code::KeyG),
-        "H" => Some(Code::KeyH),
-        "I" => Some(Code::KeyI),
-        "J" => Some(Code::KeyJ),
-        "K" => Some(Code::KeyK),
-        "L" => Some(Code::KeyL),
-        "M" => Some(Code::KeyM),
-        "N" => Some(Code::KeyN),
-        "O" => Some(Code::KeyO),
-        "P" => Some(Code::KeyP),
-        "Q" => Some(Code::KeyQ),
-        "R" => Some(Code::KeyR),
-        "S" => Some(Code::KeyS),
-        "T" => Some(Code::KeyT),
-        "U" => Some(Code::KeyU),
-        "V" => Some(Code::KeyV),
-        "W" => Some(Code::KeyW),
-        "X" => Some(Code::KeyX),
-        "Y" => Some(Code::KeyY),
-        "Z" => Some(Code::KeyZ),
-        "F1" => Some(Code::F1),
-        "F2" => Some(Code::F2),
-        "F3" => Some(Code::F3),
-        "F4" => Some(Code::F4),
-        "F5" => Some(Code::F5),
-        "F6" => Some(Code::F6),
-        "F7" => Some(Code::F7),
-        "F8" => Some(Code::F8),
-        "F9" => Some(Code::F9),
-        "F10" => Some(Code::F10),
-        "F11" => Some(Code::F11),
-        "F12" => Some(Code::F12),
-        "Space" => Some(Code::Space),
-        _ => None,
+/// Which monitor the launcher appears on when shown.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum MonitorPolicy {
+    /// Exactly where it was last left (remembered per monitor layout).
+    #[default]
+    Fixed,
+    /// The monitor under the mouse pointer.
+    Pointer,
+    /// The monitor showing the focused window.
+    FocusedWindow,
+    /// The primary monitor.
+    Primary,
+}
+
+impl MonitorPolicy {
+    pub const ALL: &[MonitorPolicy] = &[
+        MonitorPolicy::Fixed,
+        MonitorPolicy::Pointer,
+        MonitorPolicy::FocusedWindow,
+        MonitorPolicy::Primary,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MonitorPolicy::Fixed => "Fixed (last position)",
+            MonitorPolicy::Pointer => "Monitor with mouse pointer",
+            MonitorPolicy::FocusedWindow => "Monitor with focused window",
+            MonitorPolicy::Primary => "Primary monitor",
+        }
+    }
+}
+
+/// Where on the chosen monitor the launcher appears, recomputed on every show.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum PlacementPreset {
+    /// Wherever it was last dragged to.
+    #[default]
+    Remembered,
+    /// Horizontally centered, `placement_margin` below the top edge.
+    TopCenter,
+    /// Centered on the monitor.
+    Center,
+    /// Horizontally centered, `placement_margin` above the bottom edge.
+    BottomCenter,
+    /// Just below the mouse pointer.
+    NearCursor,
+}
+
+impl PlacementPreset {
+    pub const ALL: &[PlacementPreset] = &[
+        PlacementPreset::Remembered,
+        PlacementPreset::TopCenter,
+        PlacementPreset::Center,
+        PlacementPreset::BottomCenter,
+        PlacementPreset::NearCursor,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PlacementPreset::Remembered => "Remembered position",
+            PlacementPreset::TopCenter => "Top center",
+            PlacementPreset::Center => "Screen center",
+            PlacementPreset::BottomCenter => "Bottom center",
+            PlacementPreset::NearCursor => "Near mouse cursor",
+        }
+    }
+
+    /// Presets that keep a fixed distance from a screen edge.
+    pub fn uses_margin(self) -> bool {
+        matches!(self, PlacementPreset::TopCenter | PlacementPreset::BottomCenter)
+    }
+}
+
+/// Modifier-only activation, as an alternative to the hotkey chord (X11 only).
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+pub enum ModifierTap {
+    #[default]
+    Off,
+    DoubleCtrl,
+    DoubleShift,
+    DoubleAlt,
+    DoubleSuper,
+    /// A single tap of Super with no other key.
+    SuperAlone,
+}
+
+impl ModifierTap {
+    pub const ALL: &[ModifierTap] = &[
+        ModifierTap::Off,
+        ModifierTap::DoubleCtrl,
+        ModifierTap::DoubleShift,
+        ModifierTap::DoubleAlt,
+        ModifierTap::DoubleSuper,
+        ModifierTap::SuperAlone,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ModifierTap::Off => "Off",
+            ModifierTap::DoubleCtrl => "Double-tap Ctrl",
+            ModifierTap::DoubleShift => "Double-tap Shift",
+            ModifierTap::DoubleAlt => "Double-tap Alt",
+            ModifierTap::DoubleSuper => "Double-tap Win",
+            ModifierTap::SuperAlone => "Tap Win alone",
+        }
+    }
+}
+
+/// The look of the launcher bar, which a profile can override.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Appearance {
+    pub font_color: [u8; 3],
+    pub font_size: f32,
+    pub window_width: f32,
+    pub window_height: f32,
+}
+
+impl Appearance {
+    pub fn size(&self) -> (f32, f32) {
+        (self.window_width, self.window_height)
+    }
+
+    pub fn font_color32(&self) -> egui::Color32 {
+        egui::Color32::from_rgb(self.font_color[0], self.font_color[1], self.font_color[2])
     }
 }
 
+/// A named word set, e.g. "work" or "presentation". Words list the profiles
+/// they belong to; words without any are shared by all profiles.
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    /// Own appearance; None uses the base settings.
+    #[serde(default)]
+    pub appearance: Option<Appearance>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Settings {
+    /// Format version of config.json; see `migrations`.
+    #[serde(default)]
+    pub schema_version: u32,
     pub font_color: [u8; 3],
     pub window_width: f32,
     pub window_height: f32,
@@ -136,8 +235,50 @@ pub struct Settings {
     pub window_x: Option<f32>,
     #[serde(default)]
     pub window_y: Option<f32>,
+    #[serde(default)]
+    pub monitor_policy: MonitorPolicy,
+    /// Last window position per monitor layout (see `monitors::layout_key`).
+    #[serde(default)]
+    pub saved_positions: HashMap<String, [f32; 2]>,
+    #[serde(default)]
+    pub placement_preset: PlacementPreset,
+    #[serde(default = "default_placement_margin")]
+    pub placement_margin: f32,
+    #[serde(default)]
+    pub modifier_tap: ModifierTap,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+    /// Name of the profile in use; None means shared words only.
+    #[serde(default)]
+    pub active_profile: Option<String>,
+    /// Shared, read-only word files or directories layered under `magic_words`.
+    #[serde(default = "libraries::default_libraries")]
+    pub libraries: Vec<WordLibrary>,
+    /// Words read from the enabled libraries; see `load_libraries`.
+    #[serde(skip)]
+    pub library_words: Vec<LibraryWord>,
+    #[serde(skip)]
+    pub library_errors: Vec<String>,
+    /// Compiled regexes of every word's pattern; see `compile_patterns`.
+    #[serde(skip)]
+    pub patterns: commands::PatternCache,
+    /// Set when config.json exists but couldn't be read or parsed, or a reload
+    /// failed validation. Saving is refused while set, so a bad file is never
+    /// replaced by the defaults.
+    #[serde(skip)]
+    pub load_error: Option<String>,
+    /// Format to save in regardless of which config file exists. Set after a
+    /// broken config is set aside, so a TOML user doesn't end up with JSON.
+    #[serde(skip)]
+    pub save_format: Option<ConfigFormat>,
 }
 
+/// How many previous versions of config.json are kept (config.json.1 is newest).
+const BACKUP_COUNT: usize = 5;
+
+/// Keys that change on every drag; edits to only these don't rotate backups.
+const POSITION_KEYS: &[&str] = &["window_x", "window_y", "saved_positions"];
+
 fn default_font_size() -> f32 {
     16.0
 }
@@ -146,9 +287,14 @@ fn default_true() -> bool {
     true
 }
 
+fn default_placement_margin() -> f32 {
+    48.0
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            schema_version: migrations::CURRENT_VERSION,
             font_color: [0, 200, 120],
             window_width: 400.0,
             window_height: 48.0,
@@ -161,42 +307,268 @@ impl Default for Settings {
             start_at_startup: false,
             window_x: None,
             window_y: None,
+            monitor_policy: MonitorPolicy::default(),
+            saved_positions: HashMap::new(),
+            placement_preset: PlacementPreset::default(),
+            placement_margin: default_placement_margin(),
+            modifier_tap: ModifierTap::default(),
+            profiles: Vec::new(),
+            active_profile: None,
+            libraries: libraries::default_libraries(),
+            library_words: Vec::new(),
+            library_errors: Vec::new(),
+            patterns: commands::PatternCache::new(),
+            load_error: None,
+            save_format: None,
         }
     }
 }
 
 impl Settings {
-    pub fn config_path() -> PathBuf {
+    pub fn json_config_path() -> PathBuf {
         let mut p = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
         p.push("slickrun");
         p.push("config.json");
         p
     }
 
+    pub fn toml_config_path() -> PathBuf {
+        Self::json_config_path().with_extension("toml")
+    }
+
+    /// config.toml if there is one, otherwise config.json.
+    pub fn config_path() -> PathBuf {
+        let toml = Self::toml_config_path();
+        if toml.exists() { toml } else { Self::json_config_path() }
+    }
+
     pub fn load() -> Self {
         let path = Self::config_path();
-        if path.exists() {
-            match std::fs::read_to_string(&path) {
-                Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
-                Err(_) => Self::default(),
+        if !path.exists() {
+            let mut settings = Self::default();
+            settings.load_libraries();
+            return settings;
+        }
+        let loaded = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| Self::parse(&data, ConfigFormat::of(&path)));
+        match loaded {
+            Ok(mut settings) => {
+                settings.load_libraries();
+                settings
+            }
+            Err(e) => {
+                eprintln!("[SlickRun] Could not load {}: {e}", path.display());
+                Self {
+                    load_error: Some(format!("{} could not be loaded: {}", path.display(), e)),
+                    ..Self::default()
+                }
             }
-        } else {
-            Self::default()
         }
     }
 
-    pub fn save(&self) {
-        let path = Self::config_path();
+    /// Load a config (JSON or TOML), or a bare magic word list as written by
+    /// Export, from `path` without touching the live config.
+    pub fn load_from(path: &std::path::Path) -> Result<Settings, String> {
+        let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let format = ConfigFormat::of(path);
+        if format == ConfigFormat::Json && data.trim_start().starts_with('[') {
+            let magic_words = serde_json::from_str(&data).map_err(|e| e.to_string())?;
+            return Ok(Settings { magic_words, ..Settings::default() });
+        }
+        Self::parse(&data, format)
+    }
+
+    /// Parse a config file of any schema version, migrating it to the current one.
+    fn parse(data: &str, format: ConfigFormat) -> Result<Settings, String> {
+        let mut value = format.parse(data)?;
+        migrations::migrate(&mut value)?;
+        serde_json::from_value(value).map_err(|e| e.to_string())
+    }
+
+    /// Write the config atomically (temp file + rename), first rotating the
+    /// previous version into config.json.1 … config.json.5 (or config.toml.N).
+    /// A TOML config keeps its comments.
+    pub fn save(&self) -> Result<(), String> {
+        if let Some(e) = &self.load_error {
+            return Err(format!("refusing to overwrite a config that failed to load ({e})"));
+        }
+        let path = match self.save_format {
+            Some(ConfigFormat::Json) => Self::json_config_path(),
+            Some(ConfigFormat::Toml) => Self::toml_config_path(),
+            None => Self::config_path(),
+        };
         if let Some(parent) = path.parent() {
-            let _ = std::fs::create_dir_all(parent);
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
         }
-        if let Ok(data) = serde_json::to_string_pretty(self) {
-            let _ = std::fs::write(&path, data);
+        let format = ConfigFormat::of(&path);
+        let previous = std::fs::read_to_string(&path).ok();
+        let data = format.render(self, previous.as_deref())?;
+
+        if previous.is_some_and(|previous| differs_beyond_position(&previous, &data, format)) {
+            rotate_backups(&path);
+        }
+
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        {
+            use std::io::Write;
+            let mut file = std::fs::File::create(&tmp).map_err(|e| e.to_string())?;
+            file.write_all(data.as_bytes())
+                .and_then(|_| file.sync_all())
+                .map_err(|e| e.to_string())?;
         }
+        std::fs::rename(&tmp, &path).map_err(|e| e.to_string())
     }
 
-    pub fn font_color32(&self) -> egui::Color32 {
-        egui::Color32::from_rgb(self.font_color[0], self.font_color[1], self.font_color[2])
+    fn backup_path(n: usize) -> PathBuf {
+        let mut p = Self::config_path().into_os_string();
+        p.push(format!(".{}", n));
+        PathBuf::from(p)
+    }
+
+    /// Existing backups, newest first, with their modification times.
+    pub fn backups() -> Vec<(PathBuf, std::time::SystemTime)> {
+        (1..=BACKUP_COUNT)
+            .map(Self::backup_path)
+            .filter_map(|p| {
+                let modified = std::fs::metadata(&p).and_then(|m| m.modified()).ok()?;
+                Some((p, modified))
+            })
+            .collect()
+    }
+
+    /// Move an unloadable config.json out of the way (to config.json.broken)
+    /// so it can be inspected later and saving works again. The returned path
+    /// tells the format to save the replacement in (`ConfigFormat::of`).
+    pub fn set_aside_broken_config() -> Result<PathBuf, String> {
+        let path = Self::config_path();
+        let mut broken = path.clone().into_os_string();
+        broken.push(".broken");
+        let broken = PathBuf::from(broken);
+        if path.exists() {
+            std::fs::rename(&path, &broken).map_err(|e| e.to_string())?;
+        }
+        Ok(broken)
+    }
+
+    /// Load a backup in place of the broken config.json, which is set aside.
+    /// The caller saves the returned settings, in the broken file's format.
+    pub fn restore_backup(backup: &std::path::Path) -> Result<Settings, String> {
+        let data = std::fs::read_to_string(backup).map_err(|e| e.to_string())?;
+        let mut settings = Self::parse(&data, ConfigFormat::of(backup))?;
+        let broken = Self::set_aside_broken_config()?;
+        settings.save_format = Some(ConfigFormat::of(&broken));
+        Ok(settings)
+    }
+
+    /// Re-read the enabled word libraries from disk.
+    pub fn load_libraries(&mut self) {
+        let (words, errors) = libraries::load(&self.libraries);
+        for e in &errors {
+            eprintln!("[SlickRun] Word library: {e}");
+        }
+        self.library_words = words;
+        self.library_errors = errors;
+        self.compile_patterns();
+    }
+
+    /// Bring `patterns` up to date with the personal and library words of
+    /// every profile. Call after loading or applying edited settings.
+    pub fn compile_patterns(&mut self) {
+        let words = self.magic_words.iter().chain(self.library_words.iter().map(|w| &w.word));
+        commands::compile_patterns(words, &mut self.patterns);
+    }
+
+    /// Library words not overridden by a personal word with the same keyword.
+    pub fn visible_library_words(&self) -> impl Iterator<Item = &LibraryWord> {
+        self.library_words
+            .iter()
+            .filter(|w| commands::find_by_keyword(&self.magic_words, &w.word.keyword).is_none())
+    }
+
+    /// Every word that can be typed: the active profile's own words, then
+    /// shared ones, then the library words they don't override.
+    pub fn all_words(&self) -> impl Iterator<Item = &MagicWord> {
+        let active = self.active_profile().map(|p| p.name.as_str());
+        let in_profile = move |mw: &&MagicWord| active.is_some_and(|name| mw.profiles.iter().any(|p| p == name));
+        self.magic_words
+            .iter()
+            .filter(in_profile)
+            .chain(self.magic_words.iter().filter(|mw| mw.profiles.is_empty()))
+            .chain(
+                self.visible_library_words()
+                    .map(|w| &w.word)
+                    .filter(move |mw| mw.profiles.is_empty() || in_profile(mw)),
+            )
+    }
+
+    pub fn active_profile(&self) -> Option<&Profile> {
+        let name = self.active_profile.as_deref()?;
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    /// Base appearance, as edited in the Appearance tab without a profile.
+    pub fn base_appearance(&self) -> Appearance {
+        Appearance {
+            font_color: self.font_color,
+            font_size: self.font_size,
+            window_width: self.window_width,
+            window_height: self.window_height,
+        }
+    }
+
+    /// The active profile's appearance if it has its own, else the base one.
+    pub fn appearance(&self) -> Appearance {
+        self.active_profile()
+            .and_then(|p| p.appearance)
+            .unwrap_or_else(|| self.base_appearance())
+    }
+
+    /// The pattern word the whole of `input` triggers, if any.
+    pub fn match_pattern(&self, input: &str) -> Option<MagicWord> {
+        commands::find_by_pattern(self.all_words(), &self.patterns, input)
+    }
+
+    /// Look up a keyword in personal words first, then in the libraries.
+    pub fn find_word(&self, keyword: &str) -> Option<&MagicWord> {
+        self.all_words().find(|mw| mw.answers_to(keyword))
+    }
+
+    /// Record the window position, both globally and for the current monitor layout.
+    pub fn remember_position(&mut self, x: f32, y: f32) {
+        self.window_x = Some(x);
+        self.window_y = Some(y);
+        if let Some(layout) = crate::monitors::current_layout_key() {
+            self.saved_positions.insert(layout, [x, y]);
+        }
+    }
+
+    /// Describe what `hotkey` would clash with: the toggle hotkey, another
+    /// registered word's hotkey (library words included) or a desktop
+    /// shortcut in `bindings`. `skip` is the word being edited.
+    pub fn hotkey_conflict(
+        &self,
+        hotkey: &HotkeyConfig,
+        skip: Option<&MagicWord>,
+        bindings: &[conflicts::DesktopBinding],
+    ) -> Option<String> {
+        if *hotkey == self.hotkey {
+            return Some(format!("{} is the show/hide hotkey", hotkey.display_string()));
+        }
+        if let Some(binding) = conflicts::find_binding(hotkey, bindings) {
+            return Some(format!(
+                "{} is already bound to {} ({})",
+                hotkey.display_string(),
+                binding.owner,
+                binding.accelerator
+            ));
+        }
+        self.all_words()
+            .filter(|mw| !skip.is_some_and(|skip| std::ptr::eq(*mw, skip)))
+            .find(|mw| mw.hotkey.as_ref() == Some(hotkey))
+            .map(|mw| format!("{} is already used by \"{}\"", hotkey.display_string(), mw.keyword))
     }
 
     pub fn export_path() -> PathBuf {
@@ -216,11 +588,9 @@ impl Settings {
         Ok(path)
     }
 
-    pub fn import_magic_words() -> Result<Vec<MagicWord>, String> {
-        let path = Self::export_path();
-        let data = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
-        let words: Vec<MagicWord> = serde_json::from_str(&data).map_err(|e| e.to_string())?;
-        Ok(words)
+    /// Magic words from an exported word list or another config.json.
+    pub fn import_magic_words(path: &std::path::Path) -> Result<Vec<MagicWord>, String> {
+        Ok(Self::load_from(path)?.magic_words)
     }
 
     pub fn autostart_path() -> PathBuf {
@@ -248,6 +618,90 @@ impl Settings {
     }
 }
 
+/// What the Library sidebar shows.
+#[derive(PartialEq, Clone)]
+enum TagFilter {
+    All,
+    Untagged,
+    Tag(String),
+}
+
+impl TagFilter {
+    fn admits(&self, mw: &MagicWord) -> bool {
+        match self {
+            TagFilter::All => true,
+            TagFilter::Untagged => mw.tags.is_empty(),
+            TagFilter::Tag(tag) => mw.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)),
+        }
+    }
+}
+
+/// Columns of the Library table, in display order.
+#[derive(PartialEq, Clone, Copy)]
+enum LibraryColumn {
+    Keyword,
+    Aliases,
+    Target,
+    Mode,
+    StartPath,
+    Parameters,
+    Hotkey,
+    Tags,
+    Source,
+}
+
+impl LibraryColumn {
+    const ALL: [LibraryColumn; 9] = [
+        LibraryColumn::Keyword,
+        LibraryColumn::Aliases,
+        LibraryColumn::Target,
+        LibraryColumn::Mode,
+        LibraryColumn::StartPath,
+        LibraryColumn::Parameters,
+        LibraryColumn::Hotkey,
+        LibraryColumn::Tags,
+        LibraryColumn::Source,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            LibraryColumn::Keyword => "Keyword",
+            LibraryColumn::Aliases => "Aliases / Pattern",
+            LibraryColumn::Target => "Filename / URL",
+            LibraryColumn::Mode => "Mode",
+            LibraryColumn::StartPath => "Start Path",
+            LibraryColumn::Parameters => "Parameters",
+            LibraryColumn::Hotkey => "Hotkey",
+            LibraryColumn::Tags => "Tags",
+            LibraryColumn::Source => "Source",
+        }
+    }
+
+    fn initial_width(self) -> f32 {
+        match self {
+            LibraryColumn::Target => 160.0,
+            LibraryColumn::Mode => 50.0,
+            LibraryColumn::Aliases | LibraryColumn::Source => 100.0,
+            _ => 80.0,
+        }
+    }
+}
+
+/// A Library table row: one of the user's words, by index, or a library word.
+enum LibraryRow {
+    Personal(usize),
+    Shared(Box<LibraryWord>),
+}
+
+impl LibraryRow {
+    fn word<'a>(&'a self, magic_words: &'a [MagicWord]) -> &'a MagicWord {
+        match self {
+            LibraryRow::Personal(idx) => &magic_words[*idx],
+            LibraryRow::Shared(lw) => &lw.word,
+        }
+    }
+}
+
 #[derive(PartialEq, Clone)]
 enum SettingsTab {
     Library,
@@ -262,17 +716,73 @@ pub struct SettingsWindow {
     // Magic word editing
     show_edit_form: bool,
     edit_keyword: String,
+    edit_aliases: String,
+    edit_pattern: String,
+    edit_priority: i32,
     edit_filename_or_url: String,
     edit_start_mode: StartMode,
     edit_start_path: String,
     edit_parameters: String,
+    edit_hotkey_enabled: bool,
+    edit_hotkey: HotkeyConfig,
+    edit_profiles: Vec<String>,
+    edit_tags: String,
+    edit_description: String,
     editing_index: Option<usize>,
-    // Search filter
+    // Library table: selected words, where a shift-click range starts, the
+    // sort column (ascending?), and the tag typed for a bulk change
+    selected: BTreeSet<usize>,
+    select_anchor: Option<usize>,
+    library_sort: Option<(LibraryColumn, bool)>,
+    bulk_tag: String,
+    // Sample input for the edit form's Test panel
+    test_input: String,
+    // Compiled form of the edited pattern, for the Test panel
+    test_patterns: commands::PatternCache,
+    // Which hotkey editor is waiting for a key press, by id salt
+    recording_hotkey: Option<&'static str>,
+    // Toggle hotkey as currently registered, and why it failed to register
+    applied_hotkey: HotkeyConfig,
+    pub hotkey_error: Option<String>,
+    // Desktop shortcuts and other clients' X11 grabs, read once per settings session
+    desktop_bindings: Option<Vec<conflicts::DesktopBinding>>,
+    grab_probe: conflicts::GrabProbe,
+    // Last conflict check: (hotkey, warning, suggested alternatives)
+    toggle_conflict: Option<(HotkeyConfig, Option<String>, Vec<HotkeyConfig>)>,
+    // Import: file picker still open, typed path (no file picker), the previewed
+    // import, and notes from the last one
+    file_picker: Option<std::sync::mpsc::Receiver<import::PickedFile>>,
+    import_path: Option<String>,
+    pending_import: Option<PendingImport>,
+    import_report: Vec<String>,
+    // Validation of the draft, keyed by its serialized form
+    validation: Option<(String, Vec<validation::Issue>)>,
+    // Path typed into the "add library" field
+    library_path: String,
+    // Profiles: name typed into "add profile", and whose appearance is being edited
+    new_profile_name: String,
+    appearance_profile: Option<usize>,
+    // Search filter and sidebar selection
     search_filter: String,
+    tag_filter: TagFilter,
+    // Magic word lists before (undo) and after (redo) each library change
+    undo_stack: Vec<Vec<MagicWord>>,
+    redo_stack: Vec<Vec<MagicWord>>,
+    // The draft as opened, to tell whether there is anything to lose
+    opened: Settings,
+    // Settings reloaded from disk while the draft had unsaved edits
+    changed_on_disk: Option<Settings>,
+    // Words waiting for their delete to be confirmed, and the close waiting
+    // for unsaved changes to be saved or discarded
+    confirm_delete: Vec<usize>,
+    confirm_discard: bool,
     // Status message
     status_message: String,
 }
 
+/// Library changes that can be undone, oldest dropped first.
+const UNDO_LIMIT: usize = 100;
+
 impl SettingsWindow {
     pub fn new(settings: &Settings) -> Self {
         Self {
@@ -281,33 +791,398 @@ impl SettingsWindow {
             tab: SettingsTab::Library,
             show_edit_form: false,
             edit_keyword: String::new(),
+            edit_aliases: String::new(),
+            edit_pattern: String::new(),
+            edit_priority: 0,
             edit_filename_or_url: String::new(),
             edit_start_mode: StartMode::Normal,
             edit_start_path: String::new(),
             edit_parameters: String::new(),
+            edit_hotkey_enabled: false,
+            edit_hotkey: HotkeyConfig::default(),
+            edit_profiles: Vec::new(),
+            edit_tags: String::new(),
+            edit_description: String::new(),
             editing_index: None,
+            selected: BTreeSet::new(),
+            select_anchor: None,
+            library_sort: None,
+            bulk_tag: String::new(),
+            test_input: String::new(),
+            test_patterns: commands::PatternCache::new(),
+            recording_hotkey: None,
+            applied_hotkey: settings.hotkey.clone(),
+            hotkey_error: None,
+            desktop_bindings: None,
+            grab_probe: conflicts::GrabProbe::default(),
+            toggle_conflict: None,
+            file_picker: None,
+            import_path: None,
+            pending_import: None,
+            import_report: Vec::new(),
+            validation: None,
+            library_path: String::new(),
+            new_profile_name: String::new(),
+            appearance_profile: None,
             search_filter: String::new(),
+            tag_filter: TagFilter::All,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            opened: settings.clone(),
+            changed_on_disk: None,
+            confirm_delete: Vec::new(),
+            confirm_discard: false,
             status_message: String::new(),
         }
     }
 
+    /// True while a hotkey editor is capturing keys, so Escape cancels the
+    /// recording instead of closing settings.
+    pub fn is_recording_hotkey(&self) -> bool {
+        self.recording_hotkey.is_some()
+    }
+
     pub fn open(&mut self, settings: &Settings) {
         self.draft = settings.clone();
+        self.applied_hotkey = settings.hotkey.clone();
+        self.desktop_bindings = None;
+        self.grab_probe.clear();
+        self.toggle_conflict = None;
         self.clear_edit_fields();
         self.tab = SettingsTab::Library;
         self.search_filter.clear();
+        self.tag_filter = TagFilter::All;
         self.status_message.clear();
+        self.import_path = None;
+        self.pending_import = None;
+        self.import_report.clear();
+        self.appearance_profile = None;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.opened = settings.clone();
+        self.changed_on_disk = None;
+        self.confirm_discard = false;
         self.open = true;
     }
 
+    /// The config file was changed by another program and reloaded while
+    /// settings are open. An untouched draft takes it over; unsaved edits are
+    /// kept, but the user is asked so Save doesn't silently undo the change.
+    pub fn reloaded_from_disk(&mut self, settings: &Settings) {
+        if !self.open {
+            return;
+        }
+        if self.is_dirty() || self.show_edit_form {
+            self.changed_on_disk = Some(settings.clone());
+            return;
+        }
+        self.take_over(settings);
+        self.status_message = "Reloaded: the config file changed on disk".into();
+    }
+
+    /// Replace the draft with `settings`, dropping edits and undo history
+    /// that refer to the old magic words.
+    fn take_over(&mut self, settings: &Settings) {
+        self.draft = settings.clone();
+        self.opened = settings.clone();
+        self.applied_hotkey = settings.hotkey.clone();
+        self.toggle_conflict = None;
+        self.clear_edit_fields();
+        self.pending_import = None;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.changed_on_disk = None;
+    }
+
+    /// Whether the draft differs from the settings it was opened with.
+    pub fn is_dirty(&self) -> bool {
+        serde_json::to_value(&self.draft).ok() != serde_json::to_value(&self.opened).ok()
+    }
+
+    /// The profile was switched (and saved) from the tray or launcher while
+    /// settings are open; take it over so saving doesn't switch back.
+    pub fn set_active_profile(&mut self, profile: Option<String>) {
+        self.draft.active_profile = profile.clone();
+        self.opened.active_profile = profile;
+    }
+
+    /// Close unless that would lose changes; then ask first. Asking again
+    /// (a second Escape) dismisses the question.
+    pub fn request_close(&mut self) {
+        if !self.is_dirty() {
+            self.open = false;
+        } else {
+            self.confirm_discard = !self.confirm_discard;
+        }
+    }
+
+    /// Remember the magic words as they are, before a library change.
+    fn record_undo(&mut self) {
+        self.undo_stack.push(self.draft.magic_words.clone());
+        if self.undo_stack.len() > UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    fn undo(&mut self) {
+        if let Some(words) = self.undo_stack.pop() {
+            self.redo_stack.push(std::mem::replace(&mut self.draft.magic_words, words));
+            self.clear_edit_fields();
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(words) = self.redo_stack.pop() {
+            self.undo_stack.push(std::mem::replace(&mut self.draft.magic_words, words));
+            self.clear_edit_fields();
+        }
+    }
+
     fn clear_edit_fields(&mut self) {
         self.show_edit_form = false;
         self.edit_keyword.clear();
+        self.edit_aliases.clear();
+        self.edit_pattern.clear();
+        self.edit_priority = 0;
         self.edit_filename_or_url.clear();
         self.edit_start_mode = StartMode::Normal;
         self.edit_start_path.clear();
         self.edit_parameters.clear();
+        self.edit_hotkey_enabled = false;
+        self.edit_hotkey = HotkeyConfig::default();
+        self.edit_profiles.clear();
+        self.edit_tags.clear();
+        self.edit_description.clear();
         self.editing_index = None;
+        self.recording_hotkey = None;
+        self.selected.clear();
+        self.select_anchor = None;
+        self.confirm_delete.clear();
+    }
+
+    /// Fill the edit form from `mw`.
+    fn load_into_form(&mut self, mw: &MagicWord) {
+        self.edit_keyword = mw.keyword.clone();
+        self.edit_aliases = mw.aliases.join(", ");
+        self.edit_pattern = mw.pattern.clone().unwrap_or_default();
+        self.edit_priority = mw.priority;
+        self.edit_filename_or_url = mw.filename_or_url.clone();
+        self.edit_start_mode = mw.start_mode.clone();
+        self.edit_start_path = mw.start_path.clone().unwrap_or_default();
+        self.edit_parameters = mw.parameters.clone().unwrap_or_default();
+        self.edit_hotkey_enabled = mw.hotkey.is_some();
+        self.edit_hotkey = mw.hotkey.clone().unwrap_or_default();
+        self.edit_profiles = mw.profiles.clone();
+        self.edit_tags = mw.tags.join(", ");
+        self.edit_description = mw.description.clone().unwrap_or_default();
+    }
+
+    /// The magic word the edit form describes.
+    fn form_word(&self) -> MagicWord {
+        MagicWord {
+            keyword: self.edit_keyword.clone(),
+            aliases: self
+                .edit_aliases
+                .split([',', ' '])
+                .filter(|a| !a.is_empty())
+                .map(str::to_string)
+                .collect(),
+            filename_or_url: self.edit_filename_or_url.clone(),
+            start_mode: self.edit_start_mode.clone(),
+            start_path: if self.edit_start_path.is_empty() {
+                None
+            } else {
+                Some(self.edit_start_path.clone())
+            },
+            parameters: if self.edit_parameters.is_empty() {
+                None
+            } else {
+                Some(self.edit_parameters.clone())
+            },
+            hotkey: if self.edit_hotkey_enabled {
+                Some(self.edit_hotkey.clone())
+            } else {
+                None
+            },
+            profiles: self.edit_profiles.clone(),
+            pattern: (!self.edit_pattern.is_empty()).then(|| self.edit_pattern.clone()),
+            priority: self.edit_priority,
+            tags: self
+                .edit_tags
+                .split([',', ' '])
+                .map(|t| t.trim_start_matches('#'))
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect(),
+            description: (!self.edit_description.trim().is_empty())
+                .then(|| self.edit_description.trim().to_string()),
+        }
+    }
+
+    /// What the word in the edit form would do with some sample input, with
+    /// a Run button so it can be tried before saving.
+    fn show_test_panel(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("Test")
+            .id_salt("test_word")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Input:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.test_input)
+                            .hint_text("sample arguments")
+                            .desired_width(250.0),
+                    );
+                });
+                if self.edit_filename_or_url.is_empty() {
+                    ui.label(
+                        egui::RichText::new("Fill in Filename / URL to see what would run.")
+                            .small()
+                            .color(egui::Color32::GRAY),
+                    );
+                    return;
+                }
+
+                let mw = self.form_word();
+                commands::compile_patterns(std::iter::once(&mw), &mut self.test_patterns);
+                let input = self.test_input.trim();
+                let mut run = None;
+                egui::Grid::new("test_word_results")
+                    .num_columns(3)
+                    .spacing([8.0, 4.0])
+                    .show(ui, |ui| {
+                        let action = mw.resolve(input);
+                        ui.label(format!("{} {}", mw.keyword, input));
+                        ui.label(egui::RichText::new(action.describe()).monospace());
+                        if ui.small_button("Run").clicked() {
+                            run = Some(action);
+                        }
+                        ui.end_row();
+
+                        // As the launcher does it: the whole input against the
+                        // pattern, run without further arguments
+                        if mw.pattern.is_some() {
+                            ui.label(format!("/{}/", self.edit_pattern));
+                            match commands::find_by_pattern(std::iter::once(&mw), &self.test_patterns, input) {
+                                Some(matched) => {
+                                    let action = matched.resolve("");
+                                    ui.label(egui::RichText::new(action.describe()).monospace());
+                                    if ui.small_button("Run").clicked() {
+                                        run = Some(action);
+                                    }
+                                }
+                                None => {
+                                    ui.label(
+                                        egui::RichText::new("Input doesn't match the pattern")
+                                            .color(egui::Color32::GRAY),
+                                    );
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(action) = run {
+                    action.run();
+                    self.status_message = format!("Ran {} (not saved yet)", action.describe());
+                }
+            });
+    }
+
+    /// Select a personal word the way file managers do: a click selects just
+    /// it, Ctrl toggles it, Shift extends from the last clicked row through
+    /// `order` (the words as displayed). A single selection is loaded into
+    /// the edit form.
+    fn click_row(&mut self, idx: usize, order: &[usize], modifiers: egui::Modifiers) {
+        let position = |i: usize| order.iter().position(|&o| o == i);
+        let range = match (modifiers.shift, self.select_anchor.and_then(position), position(idx)) {
+            (true, Some(from), Some(to)) => Some(from.min(to)..=from.max(to)),
+            _ => None,
+        };
+        if let Some(range) = range {
+            if !modifiers.command {
+                self.selected.clear();
+            }
+            self.selected.extend(order[range].iter().copied());
+        } else if modifiers.command {
+            if !self.selected.remove(&idx) {
+                self.selected.insert(idx);
+            }
+            self.select_anchor = Some(idx);
+        } else {
+            self.selected = BTreeSet::from([idx]);
+            self.select_anchor = Some(idx);
+        }
+
+        if let [only] = self.selected.iter().copied().collect::<Vec<_>>()[..] {
+            let mw = self.draft.magic_words[only].clone();
+            self.editing_index = Some(only);
+            self.load_into_form(&mw);
+        } else {
+            self.editing_index = None;
+        }
+    }
+
+    /// Show a save failure and keep the window open so the user sees it.
+    pub fn report_save_error(&mut self, error: &str) {
+        self.status_message = format!("Not saved: {}", error);
+        self.open = true;
+    }
+
+    /// Banner shown while config.json can't be loaded. Saving stays blocked
+    /// until a backup is restored or the broken file is set aside.
+    fn show_load_error(&mut self, ui: &mut egui::Ui, error: &str) -> Option<Settings> {
+        let mut resolved = None;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.label(egui::RichText::new(error).color(egui::Color32::LIGHT_RED));
+            ui.label(
+                egui::RichText::new(
+                    "Nothing will be saved until this is resolved, so your magic words are not overwritten.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            let backups = Settings::backups();
+            if backups.is_empty() {
+                ui.label(egui::RichText::new("No backups found.").small());
+            }
+            for (path, modified) in &backups {
+                let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                if ui
+                    .button(format!("Restore {} ({})", name, format_age(*modified)))
+                    .clicked()
+                {
+                    match Settings::restore_backup(path) {
+                        Ok(settings) => {
+                            let broken = if settings.save_format == Some(ConfigFormat::Toml) {
+                                "config.toml.broken"
+                            } else {
+                                "config.json.broken"
+                            };
+                            self.status_message =
+                                format!("Restored {}; the broken file was kept as {}", name, broken);
+                            self.draft = settings.clone();
+                            resolved = Some(settings);
+                        }
+                        Err(e) => self.status_message = format!("Restore failed: {}", e),
+                    }
+                }
+            }
+            if ui.button("Start fresh (keep the broken file aside)").clicked() {
+                match Settings::set_aside_broken_config() {
+                    Ok(broken) => {
+                        self.status_message = format!("Moved the broken config to {}", broken.display());
+                        self.draft = Settings {
+                            save_format: Some(ConfigFormat::of(&broken)),
+                            ..Settings::default()
+                        };
+                        resolved = Some(self.draft.clone());
+                    }
+                    Err(e) => self.status_message = format!("Could not move the broken config: {}", e),
+                }
+            }
+        });
+        ui.add_space(8.0);
+        resolved
     }
 
     /// Draw settings UI into a child viewport's CentralPanel.
@@ -319,6 +1194,46 @@ impl SettingsWindow {
         ctx.set_visuals(egui::Visuals::dark());
 
         egui::CentralPanel::default().show(ctx, |ui| {
+            if let Some(error) = self.draft.load_error.clone() {
+                saved = self.show_load_error(ui, &error);
+            }
+            if self.confirm_discard {
+                egui::Frame::group(ui.style()).show(ui, |ui| {
+                    ui.label(egui::RichText::new("You have unsaved changes.").color(egui::Color32::YELLOW));
+                    ui.horizontal(|ui| {
+                        if ui.button("Save & Close").clicked() {
+                            saved = Some(self.draft.clone());
+                            self.open = false;
+                        }
+                        if ui.button("Discard").clicked() {
+                            self.open = false;
+                        }
+                        if ui.button("Keep editing").clicked() {
+                            self.confirm_discard = false;
+                        }
+                    });
+                });
+            }
+            if let Some(on_disk) = self.changed_on_disk.clone() {
+                egui::Frame::group(ui.style()).show(ui, |ui| {
+                    ui.label(
+                        egui::RichText::new(
+                            "The config file was changed outside SlickRun. Saving will overwrite that change.",
+                        )
+                        .color(egui::Color32::YELLOW),
+                    );
+                    ui.horizontal(|ui| {
+                        if ui.button("Load the file").clicked() {
+                            self.take_over(&on_disk);
+                            self.status_message = "Reloaded: your unsaved changes were discarded".into();
+                        }
+                        if ui.button("Keep my changes").clicked() {
+                            self.changed_on_disk = None;
+                        }
+                    });
+                });
+            }
+
             // Tab bar
             ui.horizontal(|ui| {
                 ui.selectable_value(&mut self.tab, SettingsTab::Library, "  Library  ");
@@ -360,65 +1275,449 @@ impl SettingsWindow {
 
         // Handle OS window close button
         if ctx.input(|i| i.viewport().close_requested()) {
-            self.open = false;
+            if self.is_dirty() {
+                ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+                self.confirm_discard = true;
+            } else {
+                self.open = false;
+            }
         }
 
         saved
     }
 
-    fn show_library_tab(&mut self, ui: &mut egui::Ui) {
-        // Toolbar
-        ui.horizontal(|ui| {
-            if ui.button("New").clicked() {
-                self.editing_index = None;
-                self.edit_keyword.clear();
-                self.edit_filename_or_url.clear();
-                self.edit_start_mode = StartMode::Normal;
-                self.edit_start_path.clear();
-                self.edit_parameters.clear();
-                self.show_edit_form = true;
+    /// Problems in the draft. Re-run only when the draft changes, since the
+    /// executable check walks PATH for every word.
+    fn validation_issues(&mut self) -> Vec<validation::Issue> {
+        let key = serde_json::to_string(&self.draft).unwrap_or_default();
+        match &self.validation {
+            Some((cached, issues)) if *cached == key => issues.clone(),
+            _ => {
+                let issues = validation::validate(&self.draft);
+                self.validation = Some((key, issues.clone()));
+                issues
             }
+        }
+    }
 
-            let has_selection = self.editing_index.is_some();
-
-            if ui
-                .add_enabled(has_selection, egui::Button::new("Edit"))
-                .clicked()
-            {
-                self.show_edit_form = true;
+    /// Read `path` into a pending import and show its preview.
+    fn preview_import(&mut self, path: &std::path::Path) {
+        match PendingImport::load(path) {
+            Ok(pending) => {
+                self.pending_import = Some(pending);
+                self.import_path = None;
+                self.import_report.clear();
             }
+            Err(e) => self.status_message = format!("Import failed: {}", e),
+        }
+    }
 
-            if ui
-                .add_enabled(has_selection, egui::Button::new("Delete"))
-                .clicked()
-            {
-                if let Some(idx) = self.editing_index {
-                    self.draft.magic_words.remove(idx);
-                    self.clear_edit_fields();
-                }
+    /// Path field, used when no file picker (zenity/kdialog) is installed.
+    fn show_import_path_row(&mut self, ui: &mut egui::Ui) {
+        let mut preview = false;
+        let mut cancel = false;
+        ui.horizontal(|ui| {
+            ui.label("Import from:");
+            if let Some(path) = &mut self.import_path {
+                ui.add(
+                    egui::TextEdit::singleline(path)
+                        .desired_width(300.0)
+                        .hint_text("words.json, config.json or a SlickRun .srl/.qrs"),
+                );
             }
+            preview = ui.button("Preview").clicked();
+            cancel = ui.button("Cancel").clicked();
+        });
+        if cancel {
+            self.import_path = None;
+        } else if preview {
+            let path = self.import_path.clone().unwrap_or_default();
+            self.preview_import(std::path::Path::new(path.trim()));
+        }
+    }
 
-            ui.separator();
+    /// Preview of a pending import: merge or replace, and per-entry choices
+    /// for keywords that already exist. Nothing changes until Apply.
+    fn show_import_preview(&mut self, ui: &mut egui::Ui) {
+        let Some(pending) = &mut self.pending_import else {
+            return;
+        };
+        let existing = &self.draft.magic_words;
+        let problems: Vec<Option<String>> = (0..pending.incoming.len())
+            .map(|i| pending.rename_problem(i, existing))
+            .collect();
+        let ready = pending.is_ready(existing);
+        let collisions = pending.collisions(existing);
+        let mut apply = false;
+        let mut cancel = false;
+
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            let name = pending.source.file_name().unwrap_or_default().to_string_lossy();
+            ui.strong(format!(
+                "Import {} magic words from {} ({} already exist)",
+                pending.incoming.len(),
+                name,
+                collisions
+            ));
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut pending.replace, false, "Merge into my words");
+                ui.radio_value(&mut pending.replace, true, "Replace all my words");
+            });
 
-            ui.label("Find:");
+            if pending.replace {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Your {} current magic words will be removed.",
+                        existing.len()
+                    ))
+                    .small()
+                    .color(egui::Color32::LIGHT_RED),
+                );
+            } else {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} new magic words will be added.",
+                        pending.incoming.len() - collisions
+                    ))
+                    .small(),
+                );
+                if collisions > 0 {
+                    egui::Grid::new("import_collisions")
+                        .striped(true)
+                        .num_columns(4)
+                        .spacing([12.0, 4.0])
+                        .show(ui, |ui| {
+                            ui.strong("Keyword");
+                            ui.strong("Mine");
+                            ui.strong("Theirs");
+                            ui.strong("Keep");
+                            ui.end_row();
+
+                            for (i, w) in pending.incoming.iter_mut().enumerate() {
+                                let Some(mine) = w.collides_with(existing) else {
+                                    continue;
+                                };
+                                ui.label(&w.word.keyword);
+                                ui.label(&existing[mine].filename_or_url);
+                                ui.label(&w.word.filename_or_url);
+                                ui.horizontal(|ui| {
+                                    let renaming = matches!(w.resolution, Resolution::Rename(_));
+                                    ui.radio_value(&mut w.resolution, Resolution::KeepMine, "Mine");
+                                    ui.radio_value(&mut w.resolution, Resolution::TakeTheirs, "Theirs");
+                                    if ui.radio(renaming, "Rename theirs").clicked() && !renaming {
+                                        w.resolution =
+                                            Resolution::Rename(format!("{}2", w.word.keyword));
+                                    }
+                                    if let Resolution::Rename(new_name) = &mut w.resolution {
+                                        ui.add(
+                                            egui::TextEdit::singleline(new_name).desired_width(90.0),
+                                        );
+                                    }
+                                    if let Some(problem) = &problems[i] {
+                                        ui.label(
+                                            egui::RichText::new(problem)
+                                                .small()
+                                                .color(egui::Color32::LIGHT_RED),
+                                        );
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                }
+            }
+
+            if !pending.notes.is_empty() {
+                egui::CollapsingHeader::new(format!("Conversion notes ({})", pending.notes.len()))
+                    .id_salt("import_notes")
+                    .show(ui, |ui| {
+                        for note in &pending.notes {
+                            ui.label(egui::RichText::new(note).small());
+                        }
+                    });
+            }
+
+            ui.horizontal(|ui| {
+                apply = ui.add_enabled(ready, egui::Button::new("Apply")).clicked();
+                cancel = ui.button("Cancel").clicked();
+            });
+        });
+
+        if cancel {
+            self.pending_import = None;
+        } else if apply && let Some(pending) = self.pending_import.take() {
+            self.record_undo();
+            self.import_report = pending.notes.clone();
+            self.status_message = pending.apply(&mut self.draft.magic_words);
+            self.clear_edit_fields();
+        }
+        ui.add_space(8.0);
+    }
+
+    /// Shared word libraries: enable/disable, add, remove and re-read them.
+    fn show_libraries(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(format!("Shared libraries ({})", self.draft.libraries.len()))
+            .id_salt("word_libraries")
+            .show(ui, |ui| {
+                let mut changed = false;
+                let mut remove = None;
+                for (i, library) in self.draft.libraries.iter_mut().enumerate() {
+                    let root = libraries::expand_home(&library.path);
+                    let count = self
+                        .draft
+                        .library_words
+                        .iter()
+                        .filter(|w| w.source.starts_with(&root))
+                        .count();
+                    ui.horizontal(|ui| {
+                        changed |= ui.checkbox(&mut library.enabled, &library.path).changed();
+                        if library.enabled {
+                            ui.label(egui::RichText::new(format!("{} words", count)).small().color(egui::Color32::GRAY));
+                        }
+                        if ui.small_button("Remove").clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = remove {
+                    self.draft.libraries.remove(i);
+                    changed = true;
+                }
+
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.library_path)
+                            .hint_text("File or directory, e.g. ~/src/team-words")
+                            .desired_width(280.0),
+                    );
+                    let path = self.library_path.trim().to_string();
+                    if ui.add_enabled(!path.is_empty(), egui::Button::new("Add")).clicked() {
+                        if self.draft.libraries.iter().any(|l| l.path == path) {
+                            self.status_message = format!("{} is already listed", path);
+                        } else {
+                            self.draft.libraries.push(WordLibrary { path, enabled: true });
+                            self.library_path.clear();
+                            changed = true;
+                        }
+                    }
+                    if ui.button("Reload").clicked() {
+                        changed = true;
+                    }
+                });
+                if changed {
+                    self.draft.load_libraries();
+                }
+
+                for error in &self.draft.library_errors {
+                    ui.label(egui::RichText::new(format!("⚠ {}", error)).small().color(egui::Color32::LIGHT_RED));
+                }
+                ui.label(
+                    egui::RichText::new(
+                        "Library words are read-only and shown in italics. Your own word with the same keyword overrides one.",
+                    )
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+            });
+    }
+
+    /// Changes applied to every selected word at once.
+    fn show_bulk_actions(&mut self, ui: &mut egui::Ui) {
+        let mut mode = None;
+        let mut add_tag = false;
+        let mut remove_tag = false;
+        let mut export = false;
+        let mut select_none = false;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.strong(format!("{} selected", self.selected.len()));
+                ui.separator();
+                ui.label("Mode:");
+                if ui.small_button("Normal").clicked() {
+                    mode = Some(StartMode::Normal);
+                }
+                if ui.small_button("Sudo").clicked() {
+                    mode = Some(StartMode::Sudo);
+                }
+                ui.separator();
+                ui.add(egui::TextEdit::singleline(&mut self.bulk_tag).hint_text("tag").desired_width(70.0));
+                let has_tag = !self.bulk_tag.trim().trim_start_matches('#').is_empty();
+                add_tag = ui.add_enabled(has_tag, egui::Button::new("Add tag").small()).clicked();
+                remove_tag = ui.add_enabled(has_tag, egui::Button::new("Remove tag").small()).clicked();
+                ui.separator();
+                export = ui.small_button("Export").clicked();
+                select_none = ui.small_button("Select none").clicked();
+            });
+        });
+
+        let tag = self.bulk_tag.trim().trim_start_matches('#').to_string();
+        if mode.is_some() || add_tag || remove_tag {
+            self.record_undo();
+        }
+        for &idx in &self.selected {
+            let mw = &mut self.draft.magic_words[idx];
+            if let Some(mode) = &mode {
+                mw.start_mode = mode.clone();
+            }
+            if add_tag && !mw.tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+                mw.tags.push(tag.clone());
+            }
+            if remove_tag {
+                mw.tags.retain(|t| !t.eq_ignore_ascii_case(&tag));
+            }
+        }
+        if export {
+            let words: Vec<MagicWord> = self.selected.iter().map(|&idx| self.draft.magic_words[idx].clone()).collect();
+            self.status_message = match Settings::export_magic_words(&words) {
+                Ok(path) => format!("Exported {} magic words to {}", words.len(), path.display()),
+                Err(e) => format!("Export failed: {}", e),
+            };
+        }
+        if select_none {
+            self.selected.clear();
+            self.select_anchor = None;
+        }
+    }
+
+    /// Category sidebar of the Library tab: all words, each tag, untagged.
+    fn show_tag_sidebar(&mut self, ui: &mut egui::Ui) {
+        let words: Vec<&MagicWord> = self
+            .draft
+            .magic_words
+            .iter()
+            .chain(self.draft.visible_library_words().map(|w| &w.word))
+            .collect();
+        let mut tags: Vec<(String, usize)> = Vec::new();
+        for tag in words.iter().flat_map(|mw| &mw.tags) {
+            match tags.iter_mut().find(|(t, _)| t.eq_ignore_ascii_case(tag)) {
+                Some((_, count)) => *count += 1,
+                None => tags.push((tag.clone(), 1)),
+            }
+        }
+        tags.sort_by_key(|(t, _)| t.to_lowercase());
+        let untagged = words.iter().filter(|mw| mw.tags.is_empty()).count();
+
+        ui.vertical(|ui| {
+            ui.set_width(110.0);
+            ui.selectable_value(
+                &mut self.tag_filter,
+                TagFilter::All,
+                format!("All ({})", words.len()),
+            );
+            for (tag, count) in tags {
+                let label = format!("#{} ({})", tag, count);
+                ui.selectable_value(&mut self.tag_filter, TagFilter::Tag(tag), label);
+            }
+            if untagged > 0 && untagged < words.len() {
+                ui.selectable_value(
+                    &mut self.tag_filter,
+                    TagFilter::Untagged,
+                    format!("Untagged ({})", untagged),
+                );
+            }
+        });
+    }
+
+    fn show_library_tab(&mut self, ui: &mut egui::Ui) {
+        // Ctrl+Z / Ctrl+Shift+Z (or Ctrl+Y), unless a text field has them
+        if !ui.ctx().wants_keyboard_input() {
+            let redo = ui.input_mut(|i| {
+                i.consume_shortcut(&egui::KeyboardShortcut::new(
+                    egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                    egui::Key::Z,
+                )) || i.consume_shortcut(&egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Y))
+            });
+            let undo = ui.input_mut(|i| {
+                i.consume_shortcut(&egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z))
+            });
+            if redo {
+                self.redo();
+            } else if undo {
+                self.undo();
+            }
+        }
+
+        // Bulk actions and Delete only reach words the search and tag filter show
+        let filter_lower = self.search_filter.to_lowercase();
+        let words = &self.draft.magic_words;
+        let tag_filter = &self.tag_filter;
+        self.selected.retain(|&idx| {
+            words.get(idx).is_some_and(|mw| tag_filter.admits(mw) && matches_search(mw, &filter_lower))
+        });
+
+        // Toolbar
+        ui.horizontal(|ui| {
+            if ui.button("New").clicked() {
+                self.clear_edit_fields();
+                // A new word starts in the category being looked at
+                self.edit_tags = match &self.tag_filter {
+                    TagFilter::Tag(tag) => tag.clone(),
+                    _ => String::new(),
+                };
+                self.show_edit_form = true;
+            }
+
+            if ui
+                .add_enabled(self.editing_index.is_some(), egui::Button::new("Edit"))
+                .clicked()
+            {
+                self.show_edit_form = true;
+            }
+
+            if ui
+                .add_enabled(!self.selected.is_empty(), egui::Button::new("Delete"))
+                .clicked()
+            {
+                self.confirm_delete = self.selected.iter().copied().collect();
+            }
+
+            ui.separator();
+
+            if ui
+                .add_enabled(!self.undo_stack.is_empty(), egui::Button::new("Undo"))
+                .on_hover_text("Ctrl+Z")
+                .clicked()
+            {
+                self.undo();
+            }
+            if ui
+                .add_enabled(!self.redo_stack.is_empty(), egui::Button::new("Redo"))
+                .on_hover_text("Ctrl+Shift+Z")
+                .clicked()
+            {
+                self.redo();
+            }
+
+            ui.separator();
+
+            ui.label("Find:");
             ui.add(
                 egui::TextEdit::singleline(&mut self.search_filter).desired_width(120.0),
             );
 
             ui.separator();
 
-            if ui.button("Import").clicked() {
-                match Settings::import_magic_words() {
-                    Ok(words) => {
-                        let count = words.len();
-                        self.draft.magic_words = words;
-                        self.status_message = format!("Imported {} magic words", count);
-                    }
+            let picked = self.file_picker.as_ref().and_then(|rx| match rx.try_recv() {
+                Err(std::sync::mpsc::TryRecvError::Empty) => None,
+                // A picker thread that died counts as a cancel
+                result => Some(result.unwrap_or(Ok(None))),
+            });
+            if let Some(picked) = picked {
+                self.file_picker = None;
+                match picked {
+                    Ok(Some(path)) => self.preview_import(&path),
+                    Ok(None) => {}
                     Err(e) => {
-                        self.status_message = format!("Import failed: {}", e);
+                        self.status_message = format!("No file picker ({}) — type the path instead", e);
+                        self.import_path = Some(String::new());
                     }
                 }
             }
+            let picking = self.file_picker.is_some();
+            let label = if picking { "Choosing file…" } else { "Import…" };
+            if ui.add_enabled(!picking, egui::Button::new(label)).clicked() {
+                self.file_picker = Some(import::pick_file_async(ui.ctx()));
+            }
             if ui.button("Export").clicked() {
                 match Settings::export_magic_words(&self.draft.magic_words) {
                     Ok(path) => {
@@ -431,6 +1730,51 @@ impl SettingsWindow {
             }
         });
 
+        if !self.confirm_delete.is_empty() {
+            let question = match &self.confirm_delete[..] {
+                [idx] => format!("Delete magic word \"{}\"?", self.draft.magic_words[*idx].keyword),
+                many => format!("Delete {} magic words?", many.len()),
+            };
+            let mut delete = false;
+            let mut cancel = false;
+            egui::Frame::group(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(question);
+                    delete = ui.button("Delete").clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+            if delete {
+                self.record_undo();
+                // Highest index first, so the others stay put
+                for idx in std::mem::take(&mut self.confirm_delete).into_iter().rev() {
+                    self.draft.magic_words.remove(idx);
+                }
+                self.clear_edit_fields();
+            } else if cancel {
+                self.confirm_delete.clear();
+            }
+        }
+
+        if self.selected.len() > 1 {
+            self.show_bulk_actions(ui);
+        }
+
+        if self.import_path.is_some() {
+            self.show_import_path_row(ui);
+        }
+        self.show_import_preview(ui);
+        if !self.import_report.is_empty() {
+            egui::CollapsingHeader::new(format!("Import details ({})", self.import_report.len()))
+                .id_salt("import_report")
+                .show(ui, |ui| {
+                    for line in &self.import_report {
+                        ui.label(egui::RichText::new(line).small());
+                    }
+                });
+        }
+        self.show_libraries(ui);
+
         ui.add_space(8.0);
 
         // Table
@@ -440,65 +1784,217 @@ impl SettingsWindow {
             .magic_words
             .iter()
             .enumerate()
-            .filter(|(_, mw)| {
-                filter_lower.is_empty()
-                    || mw.keyword.to_lowercase().contains(&filter_lower)
-                    || mw.filename_or_url.to_lowercase().contains(&filter_lower)
-            })
+            .filter(|(_, mw)| self.tag_filter.admits(mw) && matches_search(mw, &filter_lower))
             .map(|(i, _)| i)
             .collect();
 
-        egui::Grid::new("magic_words_table")
-            .striped(true)
-            .num_columns(5)
-            .min_col_width(60.0)
-            .spacing([12.0, 4.0])
-            .show(ui, |ui| {
-                ui.strong("Keyword");
-                ui.strong("Filename / URL");
-                ui.strong("Mode");
-                ui.strong("Start Path");
-                ui.strong("Parameters");
-                ui.end_row();
-
-                for &idx in &filtered_indices {
-                    let mw = &self.draft.magic_words[idx];
-                    let selected = self.editing_index == Some(idx);
-
-                    let resp = ui.selectable_label(selected, &mw.keyword);
-                    if resp.clicked() {
-                        let mw = &self.draft.magic_words[idx];
-                        self.editing_index = Some(idx);
-                        self.edit_keyword = mw.keyword.clone();
-                        self.edit_filename_or_url = mw.filename_or_url.clone();
-                        self.edit_start_mode = mw.start_mode.clone();
-                        self.edit_start_path = mw.start_path.clone().unwrap_or_default();
-                        self.edit_parameters = mw.parameters.clone().unwrap_or_default();
-                        self.show_edit_form = false; // just select, don't open form
-                    }
-                    if resp.double_clicked() {
-                        self.show_edit_form = true; // double-click opens edit form
-                    }
+        let library_rows: Vec<LibraryWord> = self
+            .draft
+            .visible_library_words()
+            .filter(|w| self.tag_filter.admits(&w.word) && matches_search(&w.word, &filter_lower))
+            .cloned()
+            .collect();
 
-                    ui.label(&mw.filename_or_url);
-                    ui.label(match mw.start_mode {
-                        StartMode::Normal => "Normal",
-                        StartMode::Sudo => "Sudo",
-                    });
-                    ui.label(mw.start_path.as_deref().unwrap_or(""));
-                    ui.label(mw.parameters.as_deref().unwrap_or(""));
-                    ui.end_row();
+        let issues = self.validation_issues();
+
+        // Personal words and library words in one list, each with its Source
+        // text, in list order unless a column header was clicked
+        let mut rows: Vec<(LibraryRow, String)> = filtered_indices
+            .iter()
+            .map(|&idx| {
+                let mw = &self.draft.magic_words[idx];
+                let mut source = if mw.profiles.is_empty() {
+                    "Personal".to_string()
+                } else {
+                    format!("Personal · {}", mw.profiles.join(", "))
+                };
+                let overridden = self
+                    .draft
+                    .library_words
+                    .iter()
+                    .find(|w| w.word.keyword.eq_ignore_ascii_case(&mw.keyword));
+                if let Some(w) = overridden {
+                    source = format!("{} (overrides {})", source, source_name(&w.source));
                 }
+                (LibraryRow::Personal(idx), source)
+            })
+            .chain(library_rows.into_iter().map(|lw| {
+                let source = source_name(&lw.source);
+                (LibraryRow::Shared(Box::new(lw)), source)
+            }))
+            .collect();
+        if let Some((column, ascending)) = self.library_sort {
+            let mut keyed: Vec<(String, (LibraryRow, String))> = rows
+                .into_iter()
+                .map(|row| {
+                    let mw = row.0.word(&self.draft.magic_words);
+                    (column_text(column, mw, &row.1).to_lowercase(), row)
+                })
+                .collect();
+            keyed.sort_by(|a, b| if ascending { a.0.cmp(&b.0) } else { b.0.cmp(&a.0) });
+            rows = keyed.into_iter().map(|(_, row)| row).collect();
+        }
+        // Personal words in display order, for shift-click ranges
+        let order: Vec<usize> = rows
+            .iter()
+            .filter_map(|(row, _)| match row {
+                LibraryRow::Personal(idx) => Some(*idx),
+                LibraryRow::Shared(_) => None,
+            })
+            .collect();
+
+        let mut sort_clicked = None;
+        let mut clicked = None;
+        let mut double_clicked = false;
+        let mut copy_word = None;
+
+        ui.horizontal_top(|ui| {
+            self.show_tag_sidebar(ui);
+            ui.separator();
+            ui.vertical(|ui| {
+                // Clicks select rows rather than text
+                ui.style_mut().interaction.selectable_labels = false;
+                let mut table = TableBuilder::new(ui)
+                    .id_salt("magic_words_table")
+                    .striped(true)
+                    .resizable(true)
+                    .vscroll(false)
+                    .sense(egui::Sense::click())
+                    .cell_layout(egui::Layout::left_to_right(egui::Align::Center));
+                for column in LibraryColumn::ALL {
+                    table = table.column(
+                        Column::initial(column.initial_width())
+                            .at_least(40.0)
+                            .resizable(true)
+                            .clip(true),
+                    );
+                }
+                table
+                    .header(22.0, |mut header| {
+                        for column in LibraryColumn::ALL {
+                            header.col(|ui| {
+                                let arrow = match self.library_sort {
+                                    Some((sorted, true)) if sorted == column => " ⬆",
+                                    Some((sorted, false)) if sorted == column => " ⬇",
+                                    _ => "",
+                                };
+                                let label = egui::RichText::new(format!("{}{}", column.label(), arrow)).strong();
+                                if ui.add(egui::Button::new(label).frame(false)).clicked() {
+                                    sort_clicked = Some(column);
+                                }
+                            });
+                        }
+                    })
+                    .body(|mut body| {
+                        for (row_data, source) in &rows {
+                            let mw = row_data.word(&self.draft.magic_words);
+                            body.row(20.0, |mut row| {
+                                let personal = match row_data {
+                                    LibraryRow::Personal(idx) => Some(*idx),
+                                    LibraryRow::Shared(_) => None,
+                                };
+                                row.set_selected(personal.is_some_and(|idx| self.selected.contains(&idx)));
+                                for column in LibraryColumn::ALL {
+                                    row.col(|ui| match (column, row_data) {
+                                        (LibraryColumn::Keyword, LibraryRow::Personal(idx)) => {
+                                            let word_issues: Vec<&validation::Issue> =
+                                                issues.iter().filter(|i| i.word == Some(*idx)).collect();
+                                            if !word_issues.is_empty() {
+                                                let color = if word_issues
+                                                    .iter()
+                                                    .any(|i| i.severity == validation::Severity::Error)
+                                                {
+                                                    egui::Color32::LIGHT_RED
+                                                } else {
+                                                    egui::Color32::YELLOW
+                                                };
+                                                let text = word_issues
+                                                    .iter()
+                                                    .map(|i| i.message.as_str())
+                                                    .collect::<Vec<_>>()
+                                                    .join("\n");
+                                                ui.label(egui::RichText::new("⚠").color(color))
+                                                    .on_hover_text(text);
+                                            }
+                                            ui.label(&mw.keyword);
+                                        }
+                                        (LibraryColumn::Keyword, LibraryRow::Shared(_)) => {
+                                            ui.label(egui::RichText::new(&mw.keyword).italics());
+                                        }
+                                        (LibraryColumn::Source, LibraryRow::Shared(_)) => {
+                                            ui.label(egui::RichText::new(source).color(egui::Color32::GRAY));
+                                        }
+                                        _ => {
+                                            ui.label(column_text(column, mw, source));
+                                        }
+                                    });
+                                }
+                                let mut response = row.response();
+                                // Library words are read-only; double-click copies one into
+                                // the personal list, where it overrides the library version
+                                if let LibraryRow::Shared(lw) = row_data {
+                                    response = response.on_hover_text(format!(
+                                        "{}Read-only, from {}.\nDouble-click to copy it into your own words and override it.",
+                                        mw.description.as_ref().map(|d| format!("{}\n\n", d)).unwrap_or_default(),
+                                        lw.source.display()
+                                    ));
+                                    if response.double_clicked() {
+                                        copy_word = Some(mw.clone());
+                                    }
+                                } else if let Some(description) = &mw.description {
+                                    response = response.on_hover_text(description);
+                                }
+                                if let Some(idx) = personal {
+                                    if response.clicked() {
+                                        clicked = Some(idx);
+                                    }
+                                    if response.double_clicked() {
+                                        clicked = Some(idx);
+                                        double_clicked = true;
+                                    }
+                                }
+                            });
+                        }
+                    });
             });
+        });
+
+        if let Some(column) = sort_clicked {
+            self.library_sort = match self.library_sort {
+                Some((sorted, true)) if sorted == column => Some((column, false)),
+                Some((sorted, false)) if sorted == column => None,
+                _ => Some((column, true)),
+            };
+        }
+        if let Some(idx) = clicked {
+            let modifiers = ui.input(|i| i.modifiers);
+            self.click_row(idx, &order, modifiers);
+            // double-click opens the edit form, a single click only selects
+            self.show_edit_form = double_clicked && self.editing_index.is_some();
+        }
+        if let Some(mw) = copy_word {
+            self.selected.clear();
+            self.editing_index = None;
+            self.load_into_form(&mw);
+            self.show_edit_form = true;
+        }
+
+        for issue in issues.iter().filter(|i| i.word.is_none()) {
+            ui.label(
+                egui::RichText::new(format!("⚠ {}", issue.message))
+                    .small()
+                    .color(egui::Color32::LIGHT_RED),
+            );
+        }
 
-        if filtered_indices.is_empty() && self.draft.magic_words.is_empty() {
+        if rows.is_empty() && self.draft.all_words().next().is_none() {
             ui.add_space(8.0);
             ui.label(
                 egui::RichText::new("No magic words yet. Click \"New\" to add one.")
                     .color(egui::Color32::GRAY)
                     .italics(),
             );
-        } else if filtered_indices.is_empty() {
+        } else if rows.is_empty() {
             ui.add_space(8.0);
             ui.label(
                 egui::RichText::new("No matches found.")
@@ -525,10 +2021,39 @@ impl SettingsWindow {
                     ui.text_edit_singleline(&mut self.edit_keyword);
                     ui.end_row();
 
+                    ui.label("Aliases:");
+                    ui.add(egui::TextEdit::singleline(&mut self.edit_aliases).hint_text("goo, google"));
+                    ui.end_row();
+
+                    ui.label("Pattern:");
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.edit_pattern)
+                                .hint_text("^JIRA-\\d+$ (optional)")
+                                .desired_width(200.0),
+                        );
+                        ui.add_enabled_ui(!self.edit_pattern.is_empty(), |ui| {
+                            ui.label("Priority:");
+                            ui.add(egui::DragValue::new(&mut self.edit_priority).range(-100..=100));
+                        });
+                    });
+                    ui.end_row();
+
                     ui.label("Filename / URL:");
                     ui.text_edit_singleline(&mut self.edit_filename_or_url);
                     ui.end_row();
 
+                    ui.label("Description:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.edit_description)
+                            .hint_text("What it does, shown under suggestions"),
+                    );
+                    ui.end_row();
+
+                    ui.label("Tags:");
+                    ui.add(egui::TextEdit::singleline(&mut self.edit_tags).hint_text("dev, web"));
+                    ui.end_row();
+
                     ui.label("Start Mode:");
                     ui.horizontal(|ui| {
                         ui.radio_value(&mut self.edit_start_mode, StartMode::Normal, "Normal");
@@ -543,8 +2068,76 @@ impl SettingsWindow {
                     ui.label("Parameters:");
                     ui.text_edit_singleline(&mut self.edit_parameters);
                     ui.end_row();
+
+                    ui.label("Hotkey:");
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.edit_hotkey_enabled, "");
+                        ui.add_enabled_ui(self.edit_hotkey_enabled, |ui| {
+                            hotkey_editor(ui, "mw_hotkey_key", &mut self.edit_hotkey, &mut self.recording_hotkey);
+                        });
+                    });
+                    ui.end_row();
+
+                    if !self.draft.profiles.is_empty() {
+                        ui.label("Profiles:");
+                        ui.horizontal_wrapped(|ui| {
+                            for profile in &self.draft.profiles {
+                                let mut member = self.edit_profiles.contains(&profile.name);
+                                if ui.checkbox(&mut member, &profile.name).changed() {
+                                    if member {
+                                        self.edit_profiles.push(profile.name.clone());
+                                    } else {
+                                        self.edit_profiles.retain(|p| *p != profile.name);
+                                    }
+                                }
+                            }
+                            ui.label(egui::RichText::new("(none: all profiles)").small().color(egui::Color32::GRAY));
+                        });
+                        ui.end_row();
+                    }
                 });
 
+            let pattern_error = (!self.edit_pattern.is_empty())
+                .then(|| regex::Regex::new(&self.edit_pattern).err())
+                .flatten();
+            match &pattern_error {
+                Some(e) => {
+                    ui.label(
+                        egui::RichText::new(format!("Invalid pattern: {}", e))
+                            .small()
+                            .color(egui::Color32::LIGHT_RED),
+                    );
+                }
+                None if !self.edit_pattern.is_empty() => {
+                    ui.label(
+                        egui::RichText::new(
+                            "Runs when the whole input matches. Use $1$, $2$ … or $name$ for capture groups.",
+                        )
+                        .small()
+                        .color(egui::Color32::GRAY),
+                    );
+                }
+                None => {}
+            }
+
+            let hotkey_conflict = if self.edit_hotkey_enabled {
+                self.load_desktop_bindings();
+                let bindings = self.desktop_bindings.as_deref().unwrap_or_default();
+                let editing = self.editing_index.and_then(|i| self.draft.magic_words.get(i));
+                self.draft.hotkey_conflict(&self.edit_hotkey, editing, bindings)
+            } else {
+                None
+            };
+            if let Some(conflict) = &hotkey_conflict {
+                ui.label(
+                    egui::RichText::new(format!("Hotkey conflict: {}", conflict))
+                        .small()
+                        .color(egui::Color32::LIGHT_RED),
+                );
+            } else if self.edit_hotkey_enabled {
+                conflict_detection_hint(ui);
+            }
+
             ui.label(
                 egui::RichText::new(
                     "Tip: Use $W$ for user arguments. Set Filename to @copy@ to copy params to clipboard.",
@@ -553,31 +2146,22 @@ impl SettingsWindow {
                 .color(egui::Color32::GRAY),
             );
 
+            self.show_test_panel(ui);
+
             ui.horizontal(|ui| {
                 let label = if self.editing_index.is_some() {
                     "Update"
                 } else {
                     "Add"
                 };
-                if ui.button(label).clicked()
+                if ui
+                    .add_enabled(hotkey_conflict.is_none() && pattern_error.is_none(), egui::Button::new(label))
+                    .clicked()
                     && !self.edit_keyword.is_empty()
                     && !self.edit_filename_or_url.is_empty()
                 {
-                    let mw = MagicWord {
-                        keyword: self.edit_keyword.clone(),
-                        filename_or_url: self.edit_filename_or_url.clone(),
-                        start_mode: self.edit_start_mode.clone(),
-                        start_path: if self.edit_start_path.is_empty() {
-                            None
-                        } else {
-                            Some(self.edit_start_path.clone())
-                        },
-                        parameters: if self.edit_parameters.is_empty() {
-                            None
-                        } else {
-                            Some(self.edit_parameters.clone())
-                        },
-                    };
+                    let mw = self.form_word();
+                    self.record_undo();
                     if let Some(idx) = self.editing_index {
                         self.draft.magic_words[idx] = mw;
                     } else {
@@ -595,47 +2179,237 @@ impl SettingsWindow {
     fn show_appearance_tab(&mut self, ui: &mut egui::Ui) {
         ui.add_space(8.0);
 
+        if !self.draft.profiles.is_empty() {
+            ui.horizontal(|ui| {
+                ui.label("Appearance for:");
+                let selected = self
+                    .appearance_profile
+                    .and_then(|i| self.draft.profiles.get(i))
+                    .map_or("All profiles", |p| p.name.as_str());
+                egui::ComboBox::from_id_salt("appearance_profile")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.appearance_profile, None, "All profiles");
+                        for (i, profile) in self.draft.profiles.iter().enumerate() {
+                            ui.selectable_value(&mut self.appearance_profile, Some(i), &profile.name);
+                        }
+                    });
+            });
+            ui.add_space(4.0);
+        }
+
+        let base = self.draft.base_appearance();
+        match self.appearance_profile.and_then(|i| self.draft.profiles.get_mut(i)) {
+            Some(profile) => {
+                let mut own = profile.appearance.is_some();
+                if ui.checkbox(&mut own, "Own appearance (otherwise the one for all profiles)").changed() {
+                    profile.appearance = own.then_some(base);
+                }
+                if let Some(look) = &mut profile.appearance {
+                    appearance_editor(ui, look);
+                }
+            }
+            None => {
+                let mut look = base;
+                appearance_editor(ui, &mut look);
+                self.draft.font_color = look.font_color;
+                self.draft.font_size = look.font_size;
+                self.draft.window_width = look.window_width;
+                self.draft.window_height = look.window_height;
+            }
+        }
+
+        ui.add_space(12.0);
+        ui.heading("Placement");
+
         ui.horizontal(|ui| {
-            ui.label("Font color:");
-            let mut color = self.draft.font_color;
-            ui.color_edit_button_srgb(&mut color);
-            self.draft.font_color = color;
+            ui.label("Position:");
+            egui::ComboBox::from_id_salt("placement_preset")
+                .selected_text(self.draft.placement_preset.label())
+                .show_ui(ui, |ui| {
+                    for &preset in PlacementPreset::ALL {
+                        ui.selectable_value(&mut self.draft.placement_preset, preset, preset.label());
+                    }
+                });
         });
 
-        ui.add_space(4.0);
+        if self.draft.placement_preset.uses_margin() {
+            ui.horizontal(|ui| {
+                ui.label("Edge margin:");
+                ui.add(
+                    egui::DragValue::new(&mut self.draft.placement_margin)
+                        .range(0.0..=600.0)
+                        .speed(1.0)
+                        .suffix(" px"),
+                );
+            });
+        }
 
-        ui.horizontal(|ui| {
-            ui.label("Font size:");
-            ui.add(
-                egui::DragValue::new(&mut self.draft.font_size)
-                    .range(8.0..=48.0)
-                    .speed(0.5)
-                    .suffix(" px"),
-            );
+        // The pointer decides the monitor for "near cursor"
+        ui.add_enabled_ui(self.draft.placement_preset != PlacementPreset::NearCursor, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Show on:");
+                egui::ComboBox::from_id_salt("monitor_policy")
+                    .selected_text(self.draft.monitor_policy.label())
+                    .show_ui(ui, |ui| {
+                        for &policy in MonitorPolicy::ALL {
+                            ui.selectable_value(&mut self.draft.monitor_policy, policy, policy.label());
+                        }
+                    });
+            });
         });
+        ui.label(
+            egui::RichText::new(if self.draft.placement_preset == PlacementPreset::Remembered {
+                "Dragged positions are remembered separately for each monitor layout."
+            } else {
+                "Recomputed every time the launcher is shown, so it follows resolution changes."
+            })
+            .small()
+            .color(egui::Color32::GRAY),
+        );
+    }
 
-        ui.add_space(12.0);
-        ui.heading("Window Size");
+    /// Read the desktop's own shortcuts once per settings session, from
+    /// gsettings on GNOME and kglobalshortcutsrc on Plasma. Elsewhere the
+    /// list stays empty; see `conflict_detection_hint`.
+    fn load_desktop_bindings(&mut self) {
+        if self.desktop_bindings.is_none() {
+            self.desktop_bindings = Some(match Desktop::detect() {
+                Desktop::Gnome => conflicts::gnome_bindings(),
+                Desktop::Kde => conflicts::kde_bindings(),
+                _ => Vec::new(),
+            });
+        }
+    }
 
+    /// Why the draft toggle hotkey may not fire, and up to three free alternatives.
+    /// Cached per hotkey — reading GNOME bindings and probing X11 grabs spawn
+    /// processes and connections.
+    fn check_toggle_hotkey(&mut self) -> (Option<String>, Vec<HotkeyConfig>) {
+        if let Some((_, warning, alternatives)) = self
+            .toggle_conflict
+            .as_ref()
+            .filter(|(hotkey, ..)| *hotkey == self.draft.hotkey)
+        {
+            return (warning.clone(), alternatives.clone());
+        }
+
+        let desktop = Desktop::detect();
+        self.load_desktop_bindings();
+        let bindings = self.desktop_bindings.as_deref().unwrap_or_default();
+        // Our own grab makes the applied hotkey look taken; its failure is in hotkey_error
+        let applied = &self.applied_hotkey;
+        let grab_probe = &mut self.grab_probe;
+        let mut grab_taken =
+            |hk: &HotkeyConfig| desktop == Desktop::Other && hk != applied && grab_probe.is_taken(hk);
+
+        let hotkey = &self.draft.hotkey;
+        let warning = if let Some(binding) = conflicts::find_binding(hotkey, bindings) {
+            Some(format!(
+                "{} is already bound to {} ({}), which will take precedence.",
+                hotkey.display_string(),
+                binding.owner,
+                binding.accelerator
+            ))
+        } else if grab_taken(hotkey) {
+            Some(format!("{} is already grabbed by another application.", hotkey.display_string()))
+        } else if *hotkey == self.applied_hotkey {
+            self.hotkey_error.clone()
+        } else {
+            None
+        };
+
+        let alternatives = if warning.is_some() {
+            conflicts::suggest_alternatives(hotkey, |hk| {
+                self.draft.hotkey_conflict(hk, None, bindings).is_some() || grab_taken(hk)
+            })
+        } else {
+            Vec::new()
+        };
+
+        self.toggle_conflict = Some((hotkey.clone(), warning.clone(), alternatives.clone()));
+        (warning, alternatives)
+    }
+
+    /// Profile list: which one is active, adding and removing them.
+    fn show_profiles(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Profiles");
         ui.horizontal(|ui| {
-            ui.label("Width:");
-            ui.add(
-                egui::DragValue::new(&mut self.draft.window_width)
-                    .range(200.0..=1200.0)
-                    .speed(1.0)
-                    .suffix(" px"),
-            );
+            ui.label("Active:");
+            let selected = self.draft.active_profile.clone().unwrap_or_else(|| "None (shared words only)".into());
+            egui::ComboBox::from_id_salt("active_profile")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.draft.active_profile, None, "None (shared words only)");
+                    for profile in &self.draft.profiles {
+                        ui.selectable_value(
+                            &mut self.draft.active_profile,
+                            Some(profile.name.clone()),
+                            &profile.name,
+                        );
+                    }
+                });
         });
 
+        let mut remove = None;
+        for (i, profile) in self.draft.profiles.iter().enumerate() {
+            let own_words = self
+                .draft
+                .magic_words
+                .iter()
+                .filter(|mw| mw.profiles.contains(&profile.name))
+                .count();
+            ui.horizontal(|ui| {
+                ui.label(&profile.name);
+                ui.label(egui::RichText::new(format!("{} own words", own_words)).small().color(egui::Color32::GRAY));
+                if ui.small_button("Remove").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove {
+            let name = self.draft.profiles.remove(i).name;
+            let mut now_shared = 0;
+            for mw in &mut self.draft.magic_words {
+                let before = mw.profiles.len();
+                mw.profiles.retain(|p| *p != name);
+                if before > 0 && mw.profiles.is_empty() {
+                    now_shared += 1;
+                }
+            }
+            if self.draft.active_profile.as_deref() == Some(name.as_str()) {
+                self.draft.active_profile = None;
+            }
+            self.appearance_profile = None;
+            self.status_message = format!(
+                "Removed profile \"{}\"; {} words that only belonged to it are now shared",
+                name, now_shared
+            );
+        }
+
         ui.horizontal(|ui| {
-            ui.label("Height:");
             ui.add(
-                egui::DragValue::new(&mut self.draft.window_height)
-                    .range(32.0..=200.0)
-                    .speed(1.0)
-                    .suffix(" px"),
+                egui::TextEdit::singleline(&mut self.new_profile_name)
+                    .hint_text("e.g. work")
+                    .desired_width(140.0),
             );
+            let name = self.new_profile_name.trim().to_string();
+            if ui.add_enabled(!name.is_empty(), egui::Button::new("Add profile")).clicked() {
+                if self.draft.profiles.iter().any(|p| p.name.eq_ignore_ascii_case(&name)) {
+                    self.status_message = format!("There is already a profile called \"{}\"", name);
+                } else {
+                    self.draft.profiles.push(Profile { name, appearance: None });
+                    self.new_profile_name.clear();
+                }
+            }
         });
+        ui.label(
+            egui::RichText::new(
+                "Words pick their profiles in the edit form; words without any are shared by all. Switch from the tray menu or by typing \"profile <name>\".",
+            )
+            .small()
+            .color(egui::Color32::GRAY),
+        );
     }
 
     fn show_options_tab(&mut self, ui: &mut egui::Ui) {
@@ -649,23 +2423,65 @@ impl SettingsWindow {
         ));
 
         ui.horizontal(|ui| {
-            ui.label("Modifiers:");
-            ui.checkbox(&mut self.draft.hotkey.super_key, "Win");
-            ui.checkbox(&mut self.draft.hotkey.ctrl, "Ctrl");
-            ui.checkbox(&mut self.draft.hotkey.shift, "Shift");
-            ui.checkbox(&mut self.draft.hotkey.alt, "Alt");
+            hotkey_editor(ui, "hotkey_key", &mut self.draft.hotkey, &mut self.recording_hotkey);
         });
 
+        conflict_detection_hint(ui);
+        let (warning, alternatives) = self.check_toggle_hotkey();
+        if let Some(warning) = warning {
+            ui.label(
+                egui::RichText::new(warning)
+                    .small()
+                    .color(egui::Color32::LIGHT_RED),
+            );
+            if !alternatives.is_empty() {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("Free alternatives:").small());
+                    for alt in alternatives {
+                        if ui.small_button(alt.display_string()).clicked() {
+                            self.draft.hotkey = alt;
+                        }
+                    }
+                });
+            }
+        }
+
+        let clashing: Vec<&str> = self
+            .draft
+            .all_words()
+            .filter(|mw| mw.hotkey.as_ref() == Some(&self.draft.hotkey))
+            .map(|mw| mw.keyword.as_str())
+            .collect();
+        if !clashing.is_empty() {
+            ui.label(
+                egui::RichText::new(format!(
+                    "Also assigned to magic word(s): {} — those hotkeys will be ignored.",
+                    clashing.join(", ")
+                ))
+                .small()
+                .color(egui::Color32::LIGHT_RED),
+            );
+        }
+
         ui.horizontal(|ui| {
-            ui.label("Key:");
-            egui::ComboBox::from_id_salt("hotkey_key")
-                .selected_text(&self.draft.hotkey.key)
+            ui.label("Also open with:");
+            egui::ComboBox::from_id_salt("modifier_tap")
+                .selected_text(self.draft.modifier_tap.label())
                 .show_ui(ui, |ui| {
-                    for &k in AVAILABLE_KEYS {
-                        ui.selectable_value(&mut self.draft.hotkey.key, k.to_string(), k);
+                    for &tap in ModifierTap::ALL {
+                        ui.selectable_value(&mut self.draft.modifier_tap, tap, tap.label());
                     }
                 });
         });
+        if self.draft.modifier_tap != ModifierTap::Off {
+            ui.label(
+                egui::RichText::new(
+                    "Modifier taps are detected on X11 sessions only. Tapping Win alone may also open the GNOME/KDE overview.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+        }
 
         ui.label(
             egui::RichText::new(
@@ -677,6 +2493,10 @@ impl SettingsWindow {
 
         ui.add_space(12.0);
 
+        self.show_profiles(ui);
+
+        ui.add_space(12.0);
+
         // Startup
         ui.heading("Startup");
         ui.checkbox(&mut self.draft.start_at_startup, "Start at login");
@@ -706,3 +2526,212 @@ impl SettingsWindow {
         );
     }
 }
+
+/// Font and window size fields of the Appearance tab.
+fn appearance_editor(ui: &mut egui::Ui, look: &mut Appearance) {
+    ui.horizontal(|ui| {
+        ui.label("Font color:");
+        ui.color_edit_button_srgb(&mut look.font_color);
+    });
+
+    ui.add_space(4.0);
+
+    ui.horizontal(|ui| {
+        ui.label("Font size:");
+        ui.add(
+            egui::DragValue::new(&mut look.font_size)
+                .range(8.0..=48.0)
+                .speed(0.5)
+                .suffix(" px"),
+        );
+    });
+
+    ui.add_space(12.0);
+    ui.heading("Window Size");
+
+    ui.horizontal(|ui| {
+        ui.label("Width:");
+        ui.add(
+            egui::DragValue::new(&mut look.window_width)
+                .range(200.0..=1200.0)
+                .speed(1.0)
+                .suffix(" px"),
+        );
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Height:");
+        ui.add(
+            egui::DragValue::new(&mut look.window_height)
+                .range(32.0..=200.0)
+                .speed(1.0)
+                .suffix(" px"),
+        );
+    });
+}
+
+/// Compositor keybindings on wlroots live in its own config, which we can't
+/// read and can't probe, so say that instead of implying the hotkey is free.
+fn conflict_detection_hint(ui: &mut egui::Ui) {
+    if Desktop::detect().is_wlroots() {
+        ui.label(
+            egui::RichText::new("Conflict detection is unavailable on this desktop.")
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+    }
+}
+
+/// Modifier checkboxes, key picker and a "Record" button, laid out inline.
+/// While recording, the next key press (with Ctrl/Shift/Alt held) becomes the
+/// hotkey. egui doesn't report Super on Linux or numpad/media keys at all, so
+/// those still come from the checkbox and the picker.
+fn hotkey_editor(
+    ui: &mut egui::Ui,
+    id_salt: &'static str,
+    hotkey: &mut HotkeyConfig,
+    recording: &mut Option<&'static str>,
+) {
+    ui.checkbox(&mut hotkey.super_key, "Win");
+    ui.checkbox(&mut hotkey.ctrl, "Ctrl");
+    ui.checkbox(&mut hotkey.shift, "Shift");
+    ui.checkbox(&mut hotkey.alt, "Alt");
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(&hotkey.key)
+        .width(110.0)
+        .height(300.0)
+        .show_ui(ui, |ui| {
+            for k in keys::KEYS {
+                ui.selectable_value(&mut hotkey.key, k.name.to_string(), k.name);
+            }
+        });
+
+    let is_recording = *recording == Some(id_salt);
+    let label = if is_recording { "Press keys… (Esc cancels)" } else { "Record" };
+    let button = ui.selectable_label(is_recording, label);
+    if button.clicked() {
+        *recording = if is_recording { None } else { Some(id_salt) };
+        // Otherwise Space/Enter would "click" the button instead of being recorded
+        button.surrender_focus();
+    }
+    if !is_recording || !ui.is_enabled() {
+        return;
+    }
+    ui.ctx().request_repaint();
+
+    let pressed = ui.input(|i| {
+        i.events.iter().find_map(|e| match e {
+            egui::Event::Key { key, physical_key, pressed: true, modifiers, .. } => {
+                // Physical key so Shift+1 records "1", not "!"
+                Some((physical_key.unwrap_or(*key), *modifiers))
+            }
+            _ => None,
+        })
+    });
+    let Some((key, mods)) = pressed else {
+        return;
+    };
+    if key == egui::Key::Escape && !mods.any() {
+        *recording = None;
+        return;
+    }
+    if let Some(info) = keys::from_egui(key) {
+        hotkey.key = info.name.to_string();
+        hotkey.ctrl = mods.ctrl;
+        hotkey.shift = mods.shift;
+        hotkey.alt = mods.alt;
+        *recording = None;
+    }
+}
+
+/// Shift config.json.1..4 up by one and copy the current config into config.json.1.
+fn rotate_backups(path: &std::path::Path) {
+    for n in (1..BACKUP_COUNT).rev() {
+        let from = Settings::backup_path(n);
+        if from.exists() {
+            let _ = std::fs::rename(&from, Settings::backup_path(n + 1));
+        }
+    }
+    if let Err(e) = std::fs::copy(path, Settings::backup_path(1)) {
+        eprintln!("[SlickRun] Could not back up {}: {e}", path.display());
+    }
+}
+
+/// Whether two serialized configs differ in anything but window position.
+fn differs_beyond_position(previous: &str, new: &str, format: ConfigFormat) -> bool {
+    let strip = |data: &str| {
+        format.parse(data).ok().map(|mut v| {
+            if let Some(obj) = v.as_object_mut() {
+                for key in POSITION_KEYS {
+                    obj.remove(*key);
+                }
+            }
+            v
+        })
+    };
+    match (strip(previous), strip(new)) {
+        (Some(a), Some(b)) => a != b,
+        _ => true,
+    }
+}
+
+/// Search box match on keyword, aliases, target, tag or description.
+fn matches_search(mw: &MagicWord, filter_lower: &str) -> bool {
+    filter_lower.is_empty()
+        || mw
+            .names()
+            .any(|name| name.to_lowercase().contains(filter_lower))
+        || mw.filename_or_url.to_lowercase().contains(filter_lower)
+        || mw
+            .tags
+            .iter()
+            .any(|t| t.to_lowercase().contains(filter_lower))
+        || mw
+            .description
+            .as_ref()
+            .is_some_and(|d| d.to_lowercase().contains(filter_lower))
+}
+
+/// What the Library table shows in `column` for `mw`; `source` is its Source
+/// text. Also the sort key.
+fn column_text(column: LibraryColumn, mw: &MagicWord, source: &str) -> String {
+    match column {
+        LibraryColumn::Keyword => mw.keyword.clone(),
+        LibraryColumn::Aliases => aliases_and_pattern(mw),
+        LibraryColumn::Target => mw.filename_or_url.clone(),
+        LibraryColumn::Mode => match mw.start_mode {
+            StartMode::Normal => "Normal".into(),
+            StartMode::Sudo => "Sudo".into(),
+        },
+        LibraryColumn::StartPath => mw.start_path.clone().unwrap_or_default(),
+        LibraryColumn::Parameters => mw.parameters.clone().unwrap_or_default(),
+        LibraryColumn::Hotkey => mw.hotkey.as_ref().map(|hk| hk.display_string()).unwrap_or_default(),
+        LibraryColumn::Tags => mw.tags.join(", "),
+        LibraryColumn::Source => source.to_string(),
+    }
+}
+
+/// "goo, google" and the pattern as "/^JIRA-\d+$/", for the Library table.
+fn aliases_and_pattern(mw: &MagicWord) -> String {
+    let mut parts = mw.aliases.clone();
+    if let Some(pattern) = &mw.pattern {
+        parts.push(format!("/{}/", pattern));
+    }
+    parts.join(", ")
+}
+
+/// File name of a library word's source, for the Source column.
+fn source_name(path: &std::path::Path) -> String {
+    path.file_name().unwrap_or_default().to_string_lossy().to_string()
+}
+
+/// "3 min ago", "5 h ago", "2 days ago".
+fn format_age(time: std::time::SystemTime) -> String {
+    let secs = time.elapsed().map(|d| d.as_secs()).unwrap_or(0);
+    match secs {
+        0..=59 => "just now".into(),
+        60..=3599 => format!("{} min ago", secs / 60),
+        3600..=86399 => format!("{} h ago", secs / 3600),
+        _ => format!("{} days ago", secs / 86400),
+    }
+}
@@ -0,0 +1,238 @@
+use crate::commands::{MagicWord, StartMode};
+
+/// Result of importing a Windows SlickRun library.
+#[derive(Default)]
+pub struct SrlImport {
+    pub words: Vec<MagicWord>,
+    /// Entries that were imported but changed on the way, e.g. "code: C:\...\Code.exe -> code".
+    pub notes: Vec<String>,
+    /// Entries that couldn't be converted, with the reason.
+    pub skipped: Vec<String>,
+}
+
+/// SlickRun's built-in "@...@" commands; only @copy@ has a local equivalent.
+const SUPPORTED_SPECIALS: &[&str] = &["@copy@"];
+
+/// Read a `.srl` / `.qrs` file. SlickRun writes these as INI files, one section
+/// per magic word, usually UTF-16 with a BOM.
+pub fn import_file(path: &std::path::Path) -> Result<SrlImport, String> {
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    Ok(import_str(&decode(&bytes)))
+}
+
+fn decode(bytes: &[u8]) -> String {
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => {
+            let units: Vec<u16> = rest
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).to_string(),
+        _ => String::from_utf8_lossy(bytes).to_string(),
+    }
+}
+
+/// One `[keyword]` section with its keys lowercased.
+struct Section {
+    keyword: String,
+    fields: Vec<(String, String)>,
+}
+
+impl Section {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+            .filter(|v| !v.is_empty())
+    }
+}
+
+fn parse_sections(data: &str) -> Vec<Section> {
+    let mut sections: Vec<Section> = Vec::new();
+    for line in data.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            sections.push(Section {
+                keyword: name.trim().to_string(),
+                fields: Vec::new(),
+            });
+        } else if let (Some(section), Some((key, value))) = (sections.last_mut(), line.split_once('=')) {
+            section
+                .fields
+                .push((key.trim().to_lowercase(), value.trim().to_string()));
+        }
+    }
+    sections
+}
+
+pub fn import_str(data: &str) -> SrlImport {
+    let mut result = SrlImport::default();
+    for section in parse_sections(data) {
+        match convert(&section, &mut result.notes) {
+            Ok(word) => result.words.push(word),
+            Err(reason) => result.skipped.push(format!("{}: {}", section.keyword, reason)),
+        }
+    }
+    result
+}
+
+fn convert(section: &Section, notes: &mut Vec<String>) -> Result<MagicWord, String> {
+    let keyword = section.keyword.clone();
+    if keyword.is_empty() || keyword.contains(char::is_whitespace) {
+        return Err("keyword is empty or contains spaces".into());
+    }
+    let filename = section.get("filename").ok_or("no Filename")?;
+    let mut note = |text: String| notes.push(format!("{}: {}", keyword, text));
+
+    let filename_or_url = if filename.starts_with('@') && filename.ends_with('@') {
+        if !SUPPORTED_SPECIALS.iter().any(|s| s.eq_ignore_ascii_case(filename)) {
+            return Err(format!("SlickRun command {} has no Linux equivalent", filename));
+        }
+        filename.to_lowercase()
+    } else if is_url(filename) {
+        filename.to_string()
+    } else if is_windows_path(filename) {
+        let program = local_program(filename)
+            .ok_or_else(|| format!("Windows program {} has no match on PATH", filename))?;
+        note(format!("{} -> {}", filename, program));
+        program
+    } else {
+        filename.to_string()
+    };
+
+    let mut map_variables = |text: &str| -> String {
+        // $I$ is $W$ URL-encoded; we substitute arguments verbatim
+        if text.contains("$I$") {
+            note("$I$ became $W$ (arguments are no longer URL-encoded)".into());
+        }
+        text.replace("$I$", "$W$")
+    };
+    let filename_or_url = map_variables(&filename_or_url);
+    let parameters = section.get("params").map(&mut map_variables);
+
+    let start_path = match section.get("path") {
+        Some(path) if is_windows_path(path) => {
+            note(format!("dropped Windows start path {}", path));
+            None
+        }
+        other => other.map(str::to_string),
+    };
+
+    // StartMode is the window state (normal/minimized/maximized) — nothing to map it to.
+    // RunAs asks for elevation, which is sudo here.
+    let start_mode = match section.get("runas") {
+        Some(v) if v != "0" => StartMode::Sudo,
+        _ => StartMode::Normal,
+    };
+    if section.get("startmode").is_some_and(|mode| !matches!(mode, "0" | "1" | "Normal")) {
+        note("window start mode ignored".into());
+    }
+
+    Ok(MagicWord {
+        keyword: keyword.clone(),
+        aliases: Vec::new(),
+        filename_or_url,
+        start_mode,
+        start_path,
+        parameters,
+        hotkey: None,
+        profiles: Vec::new(),
+        pattern: None,
+        priority: 0,
+        tags: Vec::new(),
+        description: section.get("notes").filter(|n| !n.is_empty()).map(str::to_string),
+    })
+}
+
+fn is_url(s: &str) -> bool {
+    s.contains("://") || s.starts_with("www.")
+}
+
+/// "C:\...", "%ProgramFiles%\...", "\\server\share" or a bare "foo.exe".
+fn is_windows_path(s: &str) -> bool {
+    let lower = s.to_lowercase();
+    s.contains('\\')
+        || s.starts_with('%')
+        || s.as_bytes().get(1) == Some(&b':')
+        || [".exe", ".bat", ".cmd", ".lnk", ".msc"]
+            .iter()
+            .any(|ext| lower.ends_with(ext))
+}
+
+/// Map a Windows program path to a command on PATH by file stem,
+/// e.g. "C:\Program Files\Mozilla Firefox\firefox.exe" -> "firefox".
+fn local_program(windows_path: &str) -> Option<String> {
+    let file = windows_path.rsplit(['\\', '/']).next()?;
+    let stem = file.rsplit_once('.').map_or(file, |(stem, _)| stem).to_lowercase();
+    let paths = std::env::var_os("PATH")?;
+    std::env::split_paths(&paths)
+        .any(|dir| dir.join(&stem).is_file())
+        .then_some(stem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIBRARY: &str = "; exported by SlickRun\r\n\
+[g]\r\nFilename=https://www.google.com/search?q=$I$\r\nNotes=Google\r\n\
+[cp]\r\nFilename=@copy@\r\nParams=$W$\r\n\
+[calc]\r\nFilename=@MultiMonitor@\r\n\
+[logs]\r\nFilename=tail\r\nParams=-f syslog\r\nPath=C:\\Windows\\Logs\r\nRunAs=1\r\n\
+[empty]\r\nNotes=no target\r\n";
+
+    fn utf16le_with_bom(text: &str) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        bytes
+    }
+
+    #[test]
+    fn decodes_utf16le_with_bom() {
+        let imported = import_str(&decode(&utf16le_with_bom(LIBRARY)));
+        let keywords: Vec<&str> = imported.words.iter().map(|w| w.keyword.as_str()).collect();
+        assert_eq!(keywords, ["g", "cp", "logs"]);
+    }
+
+    #[test]
+    fn maps_specials_variables_paths_and_runas() {
+        let imported = import_str(LIBRARY);
+        let word = |keyword: &str| {
+            imported
+                .words
+                .iter()
+                .find(|w| w.keyword == keyword)
+                .unwrap()
+        };
+
+        assert_eq!(
+            word("g").filename_or_url,
+            "https://www.google.com/search?q=$W$"
+        );
+        assert_eq!(word("g").description.as_deref(), Some("Google"));
+        assert_eq!(word("cp").filename_or_url, "@copy@");
+        assert_eq!(word("logs").start_path, None);
+        assert_eq!(word("logs").start_mode, StartMode::Sudo);
+        assert_eq!(word("cp").start_mode, StartMode::Normal);
+
+        assert_eq!(
+            imported.notes,
+            [
+                "g: $I$ became $W$ (arguments are no longer URL-encoded)",
+                "logs: dropped Windows start path C:\\Windows\\Logs",
+            ]
+        );
+        assert_eq!(
+            imported.skipped,
+            [
+                "calc: SlickRun command @MultiMonitor@ has no Linux equivalent",
+                "empty: no Filename"
+            ]
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use std::path::{Path, PathBuf};
+
+use crate::commands::MagicWord;
+use crate::libraries::expand_home;
+use crate::settings::{HotkeyConfig, Settings};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Severity {
+    /// The config is wrong regardless of the machine it runs on.
+    Error,
+    /// Depends on this machine (missing directory, program not installed).
+    Warning,
+}
+
+/// One problem found in a config.
+#[derive(Clone, Debug)]
+pub struct Issue {
+    pub severity: Severity,
+    /// Index into `magic_words`, when the problem belongs to one.
+    pub word: Option<usize>,
+    pub message: String,
+}
+
+impl Issue {
+    fn error(word: Option<usize>, message: String) -> Self {
+        Self { severity: Severity::Error, word, message }
+    }
+
+    fn warning(word: Option<usize>, message: String) -> Self {
+        Self { severity: Severity::Warning, word, message }
+    }
+}
+
+/// Shell builtins and keywords that won't be found on PATH.
+const SHELL_BUILTINS: &[&str] = &[
+    "cd", "echo", "exec", "export", "source", ".", "test", "[", "true", "false", "if", "for",
+    "while", "case", "eval", "printf", "read", "set", "unset", "env",
+];
+
+/// Check `settings` and return every problem found, in file order.
+pub fn validate(settings: &Settings) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    let mut check_range = |name: &str, value: f32, min: f32, max: f32| {
+        if !(min..=max).contains(&value) {
+            issues.push(Issue::error(
+                None,
+                format!("{} is {}, expected {}–{}", name, value, min, max),
+            ));
+        }
+    };
+    check_range("font_size", settings.font_size, 8.0, 48.0);
+    check_range("window_width", settings.window_width, 200.0, 1200.0);
+    check_range("window_height", settings.window_height, 32.0, 200.0);
+    check_range("placement_margin", settings.placement_margin, 0.0, 600.0);
+    check_range("opacity_percent", settings.opacity_percent as f32, 20.0, 100.0);
+    for profile in &settings.profiles {
+        if let Some(look) = &profile.appearance {
+            let name = |field: &str| format!("profile \"{}\": {}", profile.name, field);
+            check_range(&name("font_size"), look.font_size, 8.0, 48.0);
+            check_range(&name("window_width"), look.window_width, 200.0, 1200.0);
+            check_range(&name("window_height"), look.window_height, 32.0, 200.0);
+        }
+    }
+
+    if let Some(message) = hotkey_problem(&settings.hotkey) {
+        issues.push(Issue::error(None, format!("show/hide hotkey: {}", message)));
+    }
+
+    if let Some(name) = &settings.active_profile
+        && settings.active_profile().is_none()
+    {
+        issues.push(Issue::error(None, format!("active profile \"{}\" does not exist", name)));
+    }
+
+    for (i, mw) in settings.magic_words.iter().enumerate() {
+        let word = Some(i);
+        if mw.keyword.trim().is_empty() {
+            issues.push(Issue::error(word, "keyword is empty".into()));
+        } else if mw.keyword.contains(char::is_whitespace) {
+            issues.push(Issue::error(word, "keyword contains spaces and can never be typed".into()));
+        }
+        for alias in &mw.aliases {
+            if alias.is_empty() || alias.contains(char::is_whitespace) {
+                issues.push(Issue::error(word, format!("alias \"{}\" is empty or contains spaces", alias)));
+            } else if alias.eq_ignore_ascii_case(&mw.keyword) {
+                issues.push(Issue::warning(word, format!("alias \"{}\" repeats the keyword", alias)));
+            }
+        }
+        // The same keyword in two profiles that are never active together is fine
+        for name in mw.names() {
+            if let Some(first) = settings.magic_words[..i]
+                .iter()
+                .position(|other| other.answers_to(name) && share_a_profile(other, mw))
+            {
+                let what = if name == mw.keyword { "keyword" } else { "alias" };
+                issues.push(Issue::error(
+                    word,
+                    format!("duplicate {} \"{}\" (entry {} wins)", what, name, first + 1),
+                ));
+            }
+        }
+        if let Some(Err(e)) = mw.pattern.as_deref().map(regex::Regex::new) {
+            issues.push(Issue::error(word, format!("pattern is not a valid regular expression: {}", e)));
+        }
+        if mw.filename_or_url.trim().is_empty() {
+            issues.push(Issue::error(word, "filename/URL is empty".into()));
+        }
+        let start_path = mw.start_path.as_deref().filter(|p| !p.is_empty());
+        if let Some(path) = start_path.filter(|p| !expand_home(p).is_dir()) {
+            issues.push(Issue::warning(word, format!("start path {} is not a directory", path)));
+        }
+        if let Some(program) = unresolved_program(mw) {
+            issues.push(Issue::warning(word, format!("{} was not found on PATH", program)));
+        }
+        if let Some(message) = mw.hotkey.as_ref().and_then(hotkey_problem) {
+            issues.push(Issue::error(word, format!("hotkey: {}", message)));
+        }
+        for name in mw.profiles.iter().filter(|p| !settings.profiles.iter().any(|q| q.name == **p)) {
+            issues.push(Issue::warning(word, format!("profile \"{}\" does not exist", name)));
+        }
+    }
+    issues
+}
+
+/// Whether two words can be visible at the same time. Shared words (no
+/// profiles) are visible in every profile.
+fn share_a_profile(a: &MagicWord, b: &MagicWord) -> bool {
+    a.profiles.is_empty() || b.profiles.is_empty() || a.profiles.iter().any(|p| b.profiles.contains(p))
+}
+
+fn hotkey_problem(hotkey: &HotkeyConfig) -> Option<String> {
+    hotkey
+        .to_hotkey()
+        .is_none()
+        .then(|| format!("unknown key \"{}\"", hotkey.key))
+}
+
+/// The program a command-type magic word runs, if it can't be found.
+/// URLs, directories, @copy@ and shell builtins are never reported.
+fn unresolved_program(mw: &MagicWord) -> Option<String> {
+    let target = mw.filename_or_url.trim();
+    if target.is_empty()
+        || target.eq_ignore_ascii_case("@copy@")
+        || target.contains("://")
+        || target.starts_with("www.")
+        || expand_home(target).is_dir()
+    {
+        return None;
+    }
+    let program = target.split_whitespace().next()?;
+    if program.contains("$W$") || program.contains('=') || SHELL_BUILTINS.contains(&program) {
+        return None;
+    }
+    if program.contains('/') {
+        return (!expand_home(program).exists()).then(|| program.to_string());
+    }
+    let on_path = std::env::var_os("PATH").is_some_and(|paths| {
+        std::env::split_paths(&paths).any(|dir| is_executable(&dir.join(program)))
+    });
+    (!on_path).then(|| program.to_string())
+}
+
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+/// `slickrun --check-config [FILE]`: validate a config.json (or an exported
+/// magic word list) and print one line per problem. Returns the process exit
+/// code — 1 if there are errors, 0 if there are only warnings or nothing.
+pub fn check_config_command(path: Option<&str>) -> i32 {
+    let path = path.map(PathBuf::from).unwrap_or_else(Settings::config_path);
+    let settings = match Settings::load_from(&path) {
+        Ok(settings) => settings,
+        Err(e) => {
+            println!("{}: error: {}", path.display(), e);
+            return 1;
+        }
+    };
+
+    let issues = validate(&settings);
+    for issue in &issues {
+        let level = match issue.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        match issue.word {
+            Some(i) => println!(
+                "{}: {}: word {} \"{}\": {}",
+                path.display(),
+                level,
+                i + 1,
+                settings.magic_words[i].keyword,
+                issue.message
+            ),
+            None => println!("{}: {}: {}", path.display(), level, issue.message),
+        }
+    }
+
+    let errors = issues.iter().filter(|i| i.severity == Severity::Error).count();
+    println!(
+        "{}: {} magic words, {} error(s), {} warning(s)",
+        path.display(),
+        settings.magic_words.len(),
+        errors,
+        issues.len() - errors
+    );
+    if errors > 0 { 1 } else { 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Profile;
+    use serde_json::json;
+
+    fn word(keyword: &str, target: &str) -> MagicWord {
+        serde_json::from_value(json!({ "keyword": keyword, "filename_or_url": target })).unwrap()
+    }
+
+    fn in_profiles(mut mw: MagicWord, profiles: &[&str]) -> MagicWord {
+        mw.profiles = profiles.iter().map(|p| p.to_string()).collect();
+        mw
+    }
+
+    fn settings(magic_words: Vec<MagicWord>) -> Settings {
+        let profiles = ["work", "home"]
+            .into_iter()
+            .map(|name| Profile {
+                name: name.into(),
+                appearance: None,
+            })
+            .collect();
+        Settings {
+            magic_words,
+            profiles,
+            ..Settings::default()
+        }
+    }
+
+    fn messages(settings: &Settings) -> Vec<(Option<usize>, String)> {
+        validate(settings)
+            .into_iter()
+            .map(|i| (i.word, i.message))
+            .collect()
+    }
+
+    #[test]
+    fn duplicates_only_count_within_a_shared_profile() {
+        let mut aliased = word("w", "sh");
+        aliased.aliases = vec!["G".into()];
+        let words = vec![
+            in_profiles(word("g", "sh"), &["work"]),
+            in_profiles(word("g", "sh"), &["home"]),
+            in_profiles(word("g", "sh"), &["home", "work"]),
+            aliased,
+        ];
+        assert_eq!(
+            messages(&settings(words)),
+            [
+                (
+                    Some(2),
+                    "duplicate keyword \"g\" (entry 1 wins)".to_string()
+                ),
+                (Some(3), "duplicate alias \"G\" (entry 1 wins)".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_empty_targets_and_invalid_patterns() {
+        let mut pattern = word("p", "sh");
+        pattern.pattern = Some("(unclosed".into());
+        let issues = validate(&settings(vec![word("e", " "), pattern]));
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].message, "filename/URL is empty");
+        assert_eq!(issues[0].word, Some(0));
+        assert!(
+            issues[1]
+                .message
+                .starts_with("pattern is not a valid regular expression")
+        );
+        assert!(issues.iter().all(|i| i.severity == Severity::Error));
+    }
+
+    #[test]
+    fn reports_out_of_range_values() {
+        let mut settings = settings(Vec::new());
+        settings.font_size = 4.0;
+        settings.window_width = 1200.0;
+        settings.opacity_percent = 10;
+        settings.profiles[0].appearance = Some(crate::settings::Appearance {
+            window_height: 500.0,
+            ..settings.base_appearance()
+        });
+        assert_eq!(
+            messages(&settings),
+            [
+                (None, "font_size is 4, expected 8–48".to_string()),
+                (None, "opacity_percent is 10, expected 20–100".to_string()),
+                (
+                    None,
+                    "profile \"work\": font_size is 4, expected 8–48".to_string()
+                ),
+                (
+                    None,
+                    "profile \"work\": window_height is 500, expected 32–200".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn unresolved_program_skips_what_is_not_a_program() {
+        for target in [
+            "https://example.com/$W$",
+            "www.example.com",
+            "@copy@",
+            "cd /tmp",
+            "$W$ --version",
+            "FOO=1 make",
+            "/",
+            "sh -c true",
+        ] {
+            assert_eq!(unresolved_program(&word("w", target)), None, "{}", target);
+        }
+        let missing = "slickrun-no-such-program";
+        assert_eq!(
+            unresolved_program(&word("w", missing)).as_deref(),
+            Some(missing)
+        );
+        assert_eq!(
+            unresolved_program(&word("w", "/no/such/dir/prog --flag")).as_deref(),
+            Some("/no/such/dir/prog")
+        );
+    }
+
+    #[test]
+    fn check_config_exits_nonzero_only_on_errors() {
+        let path = std::env::temp_dir().join(format!("slickrun-check-{}.json", std::process::id()));
+        let check = |words: serde_json::Value| {
+            std::fs::write(&path, words.to_string()).unwrap();
+            check_config_command(path.to_str())
+        };
+        let warning_only =
+            check(json!([{ "keyword": "x", "filename_or_url": "slickrun-no-such-program" }]));
+        let error = check(json!([{ "keyword": "x", "filename_or_url": "" }]));
+        let unparsable = check(json!({ "magic_words": 1 }));
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(warning_only, 0);
+        assert_eq!(error, 1);
+        assert_eq!(unparsable, 1);
+    }
+}
@@ -0,0 +1,533 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+use crate::desktop::Desktop;
+use crate::kwin;
+
+/// How the launcher window is shown, moved, focused and kept on top.
+/// One implementation is picked at startup so each desktop gets exactly one
+/// code path instead of every mechanism being tried in sequence.
+pub trait WindowBackend {
+    /// Short name for logging.
+    fn name(&self) -> &'static str;
+    /// Un-minimize, raise and give keyboard focus to the launcher.
+    fn activate(&self);
+    /// Real window position as seen by the compositor.
+    fn position(&self) -> Option<(i32, i32)>;
+    /// Move the window's top-left corner to (x, y).
+    fn move_to(&self, x: i32, y: i32);
+    /// Keep the window above all others (or stop doing so).
+    fn set_always_on_top(&self, enable: bool);
+    /// Called after the window is minimized, for compositors that ignore minimize.
+    fn hide(&self) {}
+}
+
+/// Runs the command-line tools (gdbus, swaymsg, hyprctl, riverctl, …) the
+/// backends talk to the desktop through, so tests can check the argv
+/// without a desktop.
+pub trait CommandRunner: Send + Sync {
+    /// Run to completion; stdout if it started and exited successfully.
+    fn output(&self, program: &str, args: &[&str]) -> Option<String>;
+    /// Start without waiting for it.
+    fn spawn(&self, program: &str, args: &[&str]);
+}
+
+/// Runs commands for real.
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn output(&self, program: &str, args: &[&str]) -> Option<String> {
+        let output = std::process::Command::new(program).args(args).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    fn spawn(&self, program: &str, args: &[&str]) {
+        let _ = std::process::Command::new(program)
+            .args(args)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+    }
+}
+
+/// Pick the backend for this session.
+/// `gnome_extension_active` is whether our GNOME Shell extension answered on D-Bus;
+/// until the first re-login it doesn't, and plain X11 is the best we can do.
+pub fn select(
+    desktop: Desktop,
+    gnome_extension_active: bool,
+    x11_window_id: Arc<AtomicU32>,
+) -> Box<dyn WindowBackend> {
+    let has_x11 = std::env::var_os("DISPLAY").is_some();
+    let runner: Arc<dyn CommandRunner> = Arc::new(SystemRunner);
+    match desktop {
+        Desktop::Gnome if gnome_extension_active => Box::new(GnomeExtensionBackend { x11_window_id, runner }),
+        Desktop::Kde => Box::new(KwinBackend { x11_window_id, runner }),
+        Desktop::Wlroots(_) => Box::new(LayerShellBackend),
+        _ if has_x11 => Box::new(X11Backend { x11_window_id }),
+        _ => Box::new(NoopBackend),
+    }
+}
+
+/// GNOME: window management through the SlickRun Shell extension (Mutter level).
+/// Always-on-top still goes through EWMH, which Mutter honors for XWayland windows.
+pub struct GnomeExtensionBackend {
+    x11_window_id: Arc<AtomicU32>,
+    runner: Arc<dyn CommandRunner>,
+}
+
+impl WindowBackend for GnomeExtensionBackend {
+    fn name(&self) -> &'static str {
+        "GNOME Shell extension"
+    }
+
+    fn activate(&self) {
+        // When triggered from the keyboard shortcut, the shortcut command
+        // already called Activate before the FIFO. This call covers
+        // other show paths (tray menu, etc.) and is harmless if called twice.
+        self.runner.spawn("gdbus", &gnome_shell_call("com.slickrun.Toggle.Activate", &[]));
+    }
+
+    fn position(&self) -> Option<(i32, i32)> {
+        get_mutter_window_position(self.runner.as_ref())
+    }
+
+    fn move_to(&self, x: i32, y: i32) {
+        move_window_via_mutter(self.runner.as_ref(), x, y);
+    }
+
+    fn set_always_on_top(&self, enable: bool) {
+        let wid = self.x11_window_id.load(Ordering::SeqCst);
+        if wid != 0 {
+            set_x11_always_on_top(wid, enable);
+        }
+    }
+}
+
+/// KDE Plasma: activation and moves through one-shot KWin scripts.
+pub struct KwinBackend {
+    x11_window_id: Arc<AtomicU32>,
+    runner: Arc<dyn CommandRunner>,
+}
+
+impl WindowBackend for KwinBackend {
+    fn name(&self) -> &'static str {
+        "KWin script"
+    }
+
+    fn activate(&self) {
+        // KWin focus-stealing prevention ignores our own requests on Plasma Wayland
+        kwin::activate_window(self.runner.clone());
+    }
+
+    fn position(&self) -> Option<(i32, i32)> {
+        // KWin keeps XWayland geometry in sync with the compositor, so unlike
+        // Mutter the X11 root coordinates of our window are authoritative.
+        x11_window_position(self.x11_window_id.load(Ordering::SeqCst))
+    }
+
+    fn move_to(&self, x: i32, y: i32) {
+        kwin::move_window(self.runner.clone(), x, y);
+    }
+
+    fn set_always_on_top(&self, enable: bool) {
+        kwin::set_keep_above(self.runner.clone(), enable);
+    }
+}
+
+/// sway / Hyprland / river: the launcher is a wlr-layer-shell overlay
+/// (see `layer_shell`). Its anchors place it, the overlay layer keeps it on
+/// top and exclusive keyboard interactivity focuses it, so there is nothing
+/// left to do here.
+pub struct LayerShellBackend;
+
+impl WindowBackend for LayerShellBackend {
+    fn name(&self) -> &'static str {
+        "wlr-layer-shell"
+    }
+
+    fn activate(&self) {}
+
+    fn position(&self) -> Option<(i32, i32)> {
+        None
+    }
+
+    fn move_to(&self, _x: i32, _y: i32) {}
+
+    fn set_always_on_top(&self, _enable: bool) {}
+}
+
+/// Plain X11 (or XWayland without a compositor helper): EWMH client messages.
+pub struct X11Backend {
+    x11_window_id: Arc<AtomicU32>,
+}
+
+impl X11Backend {
+    fn window_id(&self) -> Option<u32> {
+        match self.x11_window_id.load(Ordering::SeqCst) {
+            0 => None,
+            wid => Some(wid),
+        }
+    }
+}
+
+impl WindowBackend for X11Backend {
+    fn name(&self) -> &'static str {
+        "X11 EWMH"
+    }
+
+    fn activate(&self) {
+        if let Some(wid) = self.window_id() {
+            activate_x11_window_by_id(wid);
+        }
+    }
+
+    fn position(&self) -> Option<(i32, i32)> {
+        x11_window_position(self.window_id()?)
+    }
+
+    fn move_to(&self, x: i32, y: i32) {
+        let Some(wid) = self.window_id() else {
+            return;
+        };
+        if let Err(e) = move_x11_window(wid, x, y) {
+            eprintln!("[SlickRun] X11 move failed: {e}");
+        }
+    }
+
+    fn set_always_on_top(&self, enable: bool) {
+        if let Some(wid) = self.window_id() {
+            set_x11_always_on_top(wid, enable);
+        }
+    }
+}
+
+/// No window management available — rely on eframe viewport commands alone.
+pub struct NoopBackend;
+
+impl WindowBackend for NoopBackend {
+    fn name(&self) -> &'static str {
+        "none"
+    }
+
+    fn activate(&self) {}
+
+    fn position(&self) -> Option<(i32, i32)> {
+        None
+    }
+
+    fn move_to(&self, _x: i32, _y: i32) {}
+
+    fn set_always_on_top(&self, _enable: bool) {}
+}
+
+/// Root-relative position of an X11 window.
+fn x11_window_position(window_id: u32) -> Option<(i32, i32)> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::ConnectionExt;
+
+    if window_id == 0 {
+        return None;
+    }
+    let (conn, screen_num) = x11rb::connect(None).ok()?;
+    let root = conn.setup().roots[screen_num].root;
+    let reply = conn
+        .translate_coordinates(window_id, root, 0, 0)
+        .ok()?
+        .reply()
+        .ok()?;
+    let (x, y) = (reply.dst_x as i32, reply.dst_y as i32);
+    if x >= 0 && y >= 0 { Some((x, y)) } else { None }
+}
+
+fn move_x11_window(window_id: u32, x: i32, y: i32) -> Result<(), Box<dyn std::error::Error>> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::*;
+
+    let (conn, _) = x11rb::connect(None)?;
+    let values = ConfigureWindowAux::new().x(x).y(y);
+    conn.configure_window(window_id, &values)?;
+    conn.flush()?;
+    Ok(())
+}
+
+/// Activate an X11 window by its stored ID.
+/// Maps the window (un-minimizes) and sends _NET_ACTIVE_WINDOW to the WM.
+pub fn activate_x11_window_by_id(window_id: u32) {
+    if let Err(e) = activate_x11_window_by_id_inner(window_id) {
+        eprintln!("[SlickRun] X11 activation by ID failed: {e}");
+    }
+}
+
+fn activate_x11_window_by_id_inner(window_id: u32) -> Result<(), Box<dyn std::error::Error>> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::*;
+
+    let (conn, screen_num) = x11rb::connect(None)?;
+    let screen = &conn.setup().roots[screen_num];
+    let root = screen.root;
+
+    // Map the window — un-minimizes at X11 level. Harmless if already mapped.
+    conn.map_window(window_id)?;
+
+    // Raise the window
+    let values = ConfigureWindowAux::new().stack_mode(StackMode::ABOVE);
+    conn.configure_window(window_id, &values)?;
+
+    // Send _NET_ACTIVE_WINDOW to the window manager for proper focus/raise
+    let net_active = conn.intern_atom(false, b"_NET_ACTIVE_WINDOW")?.reply()?.atom;
+    let data = ClientMessageData::from([
+        1u32, // source indication: 1 = application
+        0,    // timestamp
+        0,    // requestor's currently active window
+        0, 0,
+    ]);
+    let event = ClientMessageEvent {
+        response_type: CLIENT_MESSAGE_EVENT,
+        format: 32,
+        sequence: 0,
+        window: window_id,
+        type_: net_active,
+        data,
+    };
+    let mask = EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY;
+    conn.send_event(false, root, mask, event)?;
+
+    // Also directly set input focus as a fallback
+    conn.set_input_focus(InputFocus::PARENT, window_id, x11rb::CURRENT_TIME)?;
+
+    conn.flush()?;
+    eprintln!("[SlickRun] Activated window 0x{:x} (map+raise+activate+focus)", window_id);
+    Ok(())
+}
+
+/// gdbus arguments calling `method` of our GNOME Shell extension.
+fn gnome_shell_call<'a>(method: &'a str, args: &[&'a str]) -> Vec<&'a str> {
+    let mut argv = vec![
+        "call", "--session",
+        "--dest", "org.gnome.Shell",
+        "--object-path", "/com/slickrun/Toggle",
+        "--method", method,
+    ];
+    argv.extend_from_slice(args);
+    argv
+}
+
+/// Query Mutter for the SlickRun window position via the GNOME Shell extension.
+fn get_mutter_window_position(runner: &dyn CommandRunner) -> Option<(i32, i32)> {
+    let output = runner.output("gdbus", &gnome_shell_call("com.slickrun.Toggle.GetPosition", &[]))?;
+    // Output format: "(x, y)\n"
+    let s = output.trim().trim_start_matches('(').trim_end_matches(')');
+    let mut parts = s.split(',');
+    let x: i32 = parts.next()?.trim().parse().ok()?;
+    let y: i32 = parts.next()?.trim().parse().ok()?;
+    if x >= 0 && y >= 0 { Some((x, y)) } else { None }
+}
+
+/// Move the SlickRun window via the GNOME Shell extension (Mutter level).
+/// This is the only way to position a window on GNOME Wayland.
+fn move_window_via_mutter(runner: &dyn CommandRunner, x: i32, y: i32) {
+    let (x_arg, y_arg) = (x.to_string(), y.to_string());
+    runner.spawn("gdbus", &gnome_shell_call("com.slickrun.Toggle.MoveWindow", &[&x_arg, &y_arg]));
+    eprintln!("[SlickRun] Moving window via Mutter to ({}, {})", x, y);
+}
+
+/// Set _NET_WM_STATE_ABOVE on an X11 window for always-on-top.
+fn set_x11_always_on_top(window_id: u32, enable: bool) {
+    if let Err(e) = set_x11_always_on_top_inner(window_id, enable) {
+        eprintln!("[SlickRun] X11 always-on-top failed: {e}");
+    }
+}
+
+fn set_x11_always_on_top_inner(window_id: u32, enable: bool) -> Result<(), Box<dyn std::error::Error>> {
+    use x11rb::connection::Connection;
+    use x11rb::protocol::xproto::*;
+
+    let (conn, screen_num) = x11rb::connect(None)?;
+    let screen = &conn.setup().roots[screen_num];
+    let root = screen.root;
+
+    let net_wm_state = conn.intern_atom(false, b"_NET_WM_STATE")?.reply()?.atom;
+    let net_wm_state_above = conn.intern_atom(false, b"_NET_WM_STATE_ABOVE")?.reply()?.atom;
+
+    let action = if enable { 1u32 } else { 0u32 }; // 1 = _NET_WM_STATE_ADD, 0 = _NET_WM_STATE_REMOVE
+    let data = ClientMessageData::from([action, net_wm_state_above, 0, 1, 0]);
+    let event = ClientMessageEvent {
+        response_type: CLIENT_MESSAGE_EVENT,
+        format: 32,
+        sequence: 0,
+        window: window_id,
+        type_: net_wm_state,
+        data,
+    };
+    let mask = EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY;
+    conn.send_event(false, root, mask, event)?;
+    conn.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+
+    /// Records every command instead of running it, and answers `output`
+    /// calls from `reply`.
+    struct FakeRunner {
+        calls: Mutex<Vec<Vec<String>>>,
+        scripts: Mutex<Vec<String>>,
+        reply: fn(&[&str]) -> Option<String>,
+    }
+
+    impl FakeRunner {
+        fn new(reply: fn(&[&str]) -> Option<String>) -> Arc<Self> {
+            Arc::new(FakeRunner {
+                calls: Mutex::new(Vec::new()),
+                scripts: Mutex::new(Vec::new()),
+                reply,
+            })
+        }
+
+        fn record(&self, program: &str, args: &[&str]) {
+            let mut argv = vec![program.to_string()];
+            argv.extend(args.iter().map(|a| a.to_string()));
+            self.calls.lock().unwrap().push(argv);
+            // KWin scripts are removed once run; keep what they said
+            for path in args.iter().filter(|a| a.ends_with(".js")) {
+                if let Ok(source) = std::fs::read_to_string(path) {
+                    self.scripts.lock().unwrap().push(source);
+                }
+            }
+        }
+
+        /// The commands run so far, waiting up to a second for at least `n`
+        /// (KWin scripts run on a background thread).
+        fn calls(&self, n: usize) -> Vec<Vec<String>> {
+            let start = Instant::now();
+            while self.calls.lock().unwrap().len() < n && start.elapsed() < Duration::from_secs(1) {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl CommandRunner for FakeRunner {
+        fn output(&self, program: &str, args: &[&str]) -> Option<String> {
+            self.record(program, args);
+            let mut argv = vec![program];
+            argv.extend_from_slice(args);
+            (self.reply)(&argv)
+        }
+
+        fn spawn(&self, program: &str, args: &[&str]) {
+            self.record(program, args);
+        }
+    }
+
+    fn argv(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn gnome_calls_the_shell_extension() {
+        let runner = FakeRunner::new(|argv| {
+            argv.last()
+                .is_some_and(|m| m.ends_with("GetPosition"))
+                .then(|| "(120, 45)\n".into())
+        });
+        let backend = GnomeExtensionBackend {
+            x11_window_id: Arc::new(AtomicU32::new(0)),
+            runner: runner.clone(),
+        };
+        let call = |method: &str, args: &[&str]| {
+            let mut v = argv(&[
+                "gdbus",
+                "call",
+                "--session",
+                "--dest",
+                "org.gnome.Shell",
+                "--object-path",
+                "/com/slickrun/Toggle",
+                "--method",
+                method,
+            ]);
+            v.extend(argv(args));
+            v
+        };
+
+        backend.activate();
+        backend.move_to(300, 20);
+        assert_eq!(backend.position(), Some((120, 45)));
+        assert_eq!(
+            runner.calls(3),
+            vec![
+                call("com.slickrun.Toggle.Activate", &[]),
+                call("com.slickrun.Toggle.MoveWindow", &["300", "20"]),
+                call("com.slickrun.Toggle.GetPosition", &[]),
+            ]
+        );
+    }
+
+    #[test]
+    fn kwin_loads_runs_and_unloads_a_script() {
+        let runner = FakeRunner::new(|argv| match argv.last() {
+            Some(&"slickrun-move") if argv.contains(&"org.kde.kwin.Scripting.loadScript") => {
+                Some("(int32 7,)\n".into())
+            }
+            _ => Some("()\n".into()),
+        });
+        let backend = KwinBackend {
+            x11_window_id: Arc::new(AtomicU32::new(0)),
+            runner: runner.clone(),
+        };
+        backend.move_to(300, 20);
+
+        let calls = runner.calls(4);
+        let method = |call: &Vec<String>| {
+            let at = call.iter().position(|a| a == "--method").unwrap();
+            (call[at - 1].clone(), call[at + 1].clone())
+        };
+        assert_eq!(calls.len(), 4);
+        assert!(
+            calls
+                .iter()
+                .all(|c| c[..5] == argv(&["gdbus", "call", "--session", "--dest", "org.kde.KWin"]))
+        );
+        assert_eq!(
+            method(&calls[0]),
+            (
+                "/Scripting".into(),
+                "org.kde.kwin.Scripting.unloadScript".into()
+            )
+        );
+        assert_eq!(
+            method(&calls[1]),
+            (
+                "/Scripting".into(),
+                "org.kde.kwin.Scripting.loadScript".into()
+            )
+        );
+        assert!(calls[1][calls[1].len() - 2].ends_with("slickrun-move.js"));
+        assert_eq!(
+            method(&calls[2]),
+            (
+                "/Scripting/Script7".into(),
+                "org.kde.kwin.Script.run".into()
+            )
+        );
+        assert_eq!(
+            method(&calls[3]),
+            (
+                "/Scripting".into(),
+                "org.kde.kwin.Scripting.unloadScript".into()
+            )
+        );
+
+        let scripts = runner.scripts.lock().unwrap();
+        assert!(scripts[0].contains("x: 300, y: 20"));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::keys;
+use crate::settings::HotkeyConfig;
+use crate::window_backend::CommandRunner;
+
+/// wlroots-based compositors we bind the toggle shortcut on over their IPC.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Compositor {
+    Sway,
+    Hyprland,
+    River,
+}
+
+fn swaymsg(runner: &dyn CommandRunner, command: &str) -> Option<String> {
+    runner.output("swaymsg", &[command])
+}
+
+fn hyprctl(runner: &dyn CommandRunner, args: &[&str]) -> Option<String> {
+    runner.output("hyprctl", args)
+}
+
+fn riverctl(runner: &dyn CommandRunner, args: &[&str]) -> bool {
+    runner.output("riverctl", args).is_some()
+}
+
+/// Convert HotkeyConfig to a sway/river style binding, e.g. "Mod4+Shift+q"
+fn hotkey_to_sway_binding(hotkey: &HotkeyConfig) -> String {
+    let mut parts = Vec::new();
+    if hotkey.super_key {
+        parts.push("Mod4".to_string());
+    }
+    if hotkey.ctrl {
+        parts.push("Ctrl".to_string());
+    }
+    if hotkey.shift {
+        parts.push("Shift".to_string());
+    }
+    if hotkey.alt {
+        parts.push("Mod1".to_string());
+    }
+    parts.push(keys::keysym(&hotkey.key).to_string());
+    parts.join("+")
+}
+
+/// Hyprland takes modifiers and key as separate fields, e.g. ("SUPER SHIFT", "Q")
+fn hotkey_to_hyprland_binding(hotkey: &HotkeyConfig) -> (String, String) {
+    let mut mods = Vec::new();
+    if hotkey.super_key {
+        mods.push("SUPER");
+    }
+    if hotkey.ctrl {
+        mods.push("CTRL");
+    }
+    if hotkey.shift {
+        mods.push("SHIFT");
+    }
+    if hotkey.alt {
+        mods.push("ALT");
+    }
+    (mods.join(" "), keys::keysym(&hotkey.key).to_string())
+}
+
+/// River takes modifiers ("Super+Shift", or "None") and key as separate arguments
+fn hotkey_to_river_binding(hotkey: &HotkeyConfig) -> (String, String) {
+    let mut mods = Vec::new();
+    if hotkey.super_key {
+        mods.push("Super");
+    }
+    if hotkey.ctrl {
+        mods.push("Control");
+    }
+    if hotkey.shift {
+        mods.push("Shift");
+    }
+    if hotkey.alt {
+        mods.push("Alt");
+    }
+    let mods = if mods.is_empty() { "None".to_string() } else { mods.join("+") };
+    (mods, keys::keysym(&hotkey.key).to_string())
+}
+
+/// Bind the toggle shortcut at runtime through compositor IPC.
+/// wlroots compositors have no shortcut registry — bindings live in the
+/// compositor config — so this is redone on every launch.
+pub fn register_shortcut(
+    runner: &dyn CommandRunner,
+    compositor: Compositor,
+    hotkey: &HotkeyConfig,
+    pipe_path: &std::path::Path,
+) {
+    let command = format!("echo t > {}", pipe_path.display());
+    let ok = match compositor {
+        Compositor::Sway => swaymsg(runner, &format!(
+            "bindsym {} exec '{}'",
+            hotkey_to_sway_binding(hotkey),
+            command
+        ))
+        .is_some(),
+        Compositor::Hyprland => {
+            let (mods, key) = hotkey_to_hyprland_binding(hotkey);
+            hyprctl(runner, &["keyword", "bind", &format!("{},{},exec,{}", mods, key, command)]).is_some()
+        }
+        Compositor::River => {
+            let (mods, key) = hotkey_to_river_binding(hotkey);
+            riverctl(runner, &["map", "normal", &mods, &key, "spawn", &command])
+        }
+    };
+    if ok {
+        eprintln!("[SlickRun] Registered {:?} shortcut: {} -> FIFO", compositor, hotkey.display_string());
+    } else {
+        eprintln!("[SlickRun] Failed to register {:?} shortcut — bind it in your compositor config to: {}", compositor, command);
+    }
+}
+
+/// Remove a previously registered toggle shortcut.
+pub fn unregister_shortcut(runner: &dyn CommandRunner, compositor: Compositor, hotkey: &HotkeyConfig) {
+    match compositor {
+        Compositor::Sway => {
+            let _ = swaymsg(runner, &format!("unbindsym {}", hotkey_to_sway_binding(hotkey)));
+        }
+        Compositor::Hyprland => {
+            let (mods, key) = hotkey_to_hyprland_binding(hotkey);
+            let _ = hyprctl(runner, &["keyword", "unbind", &format!("{},{}", mods, key)]);
+        }
+        Compositor::River => {
+            let (mods, key) = hotkey_to_river_binding(hotkey);
+            riverctl(runner, &["unmap", "normal", &mods, &key]);
+        }
+    }
+}